@@ -0,0 +1,468 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reading and writing sub-word fields of integers in place.
+//!
+//! [`Exists`] methods like [`get_bits`](Exists::get_bits) and
+//! [`set_bits`](Exists::set_bits) access a range of bits of an unsigned integer,
+//! counting from the least significant bit. The [`bitfields!`](crate::bitfields!)
+//! macro generates a wrapper with a named getter and setter for each field, which
+//! reads and writes the whole integer with normal or [`Volatile`] access.
+//!
+//! # Examples
+//! ```
+//! use exists_ref::{bitfields, Exists};
+//!
+//! bitfields! {
+//!     /// A status register.
+//!     pub struct Status(u32) {
+//!         /// Set when the device is ready.
+//!         pub ready, set_ready: 0;
+//!         pub code, set_code: 4..8;
+//!     }
+//! }
+//!
+//! let mut reg = 0u32;
+//! let mut status = Status::new_volatile(Exists::from_mut(&mut reg));
+//! status.set_ready(true);
+//! status.set_code(0xa);
+//! assert!(status.ready());
+//! assert_eq!(status.code(), 0xa);
+//! assert_eq!(reg, 0xa1);
+//! ```
+
+use core::ops::{Bound, RangeBounds};
+
+use crate::slice::{BitOrder, BitStore, Lsb0};
+use crate::Exists;
+
+/// How a [`bitfields!`](crate::bitfields!) wrapper reads and writes its integer.
+pub trait Access {
+    /// Reads the value at `e`.
+    fn read<T: Copy>(e: &Exists<T>) -> T;
+
+    /// Writes `value` to `e`.
+    fn write<T: Copy>(e: &mut Exists<T>, value: T);
+}
+
+/// Access with [`Exists::get`] and [`Exists::set`].
+pub enum Normal {}
+
+/// Access with [`Exists::get_volatile`] and [`Exists::set_volatile`].
+pub enum Volatile {}
+
+impl Access for Normal {
+    #[inline]
+    fn read<T: Copy>(e: &Exists<T>) -> T {
+        e.get()
+    }
+
+    #[inline]
+    fn write<T: Copy>(e: &mut Exists<T>, value: T) {
+        e.set(value)
+    }
+}
+
+impl Access for Volatile {
+    #[inline]
+    fn read<T: Copy>(e: &Exists<T>) -> T {
+        e.get_volatile()
+    }
+
+    #[inline]
+    fn write<T: Copy>(e: &mut Exists<T>, value: T) {
+        e.set_volatile(value)
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn bit_range_fail(lo: u32, hi: u32, bits: u32) -> ! {
+    panic!(
+        "bit range {}..{} out of bounds for a {}-bit integer",
+        lo, hi, bits
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn bit_index_fail(bit: u32, bits: u32) -> ! {
+    panic!("bit {} out of bounds for a {}-bit integer", bit, bits)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn field_value_fail(width: u32) -> ! {
+    panic!("value does not fit in a {}-bit field", width)
+}
+
+/// Returns the bounds of a bit range of `T`.
+#[inline]
+#[track_caller]
+fn bit_range<T: BitStore>(range: impl RangeBounds<u32>) -> (u32, u32) {
+    let lo = match range.start_bound() {
+        Bound::Included(&lo) => lo,
+        Bound::Excluded(&lo) => lo.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let hi = match range.end_bound() {
+        Bound::Included(&hi) => hi.saturating_add(1),
+        Bound::Excluded(&hi) => hi,
+        Bound::Unbounded => T::BITS,
+    };
+    if lo > hi || hi > T::BITS {
+        bit_range_fail(lo, hi, T::BITS);
+    }
+    (lo, hi)
+}
+
+/// Returns the mask of the low `width` bits.
+#[inline]
+fn low_mask<T: BitStore>(width: u32) -> T {
+    if width == 0 {
+        T::ZERO
+    } else {
+        Lsb0::range_mask(0, width)
+    }
+}
+
+impl<T: BitStore> Exists<T> {
+    /// Returns the bits in `range`, shifted down to the least significant bits.
+    ///
+    /// Bit 0 is the least significant bit.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds for `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::Exists;
+    /// let x = 0xabcd_u16;
+    /// assert_eq!(Exists::from_ref(&x).get_bits(4..12), 0xbc);
+    /// assert_eq!(Exists::from_ref(&x).get_bits(12..), 0xa);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn get_bits(&self, range: impl RangeBounds<u32>) -> T {
+        let (lo, hi) = bit_range::<T>(range);
+        if lo == hi {
+            return T::ZERO;
+        }
+        (self.get() >> lo) & low_mask(hi - lo)
+    }
+
+    /// Sets the bits in `range` to the least significant bits of `value`, leaving
+    /// the other bits unchanged.
+    ///
+    /// This reads and then writes the whole value.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds for `T`, or `value` has bits set outside
+    /// of the low `range.len()` bits.
+    #[inline]
+    #[track_caller]
+    pub fn set_bits(&mut self, range: impl RangeBounds<u32>, value: T) {
+        let (lo, hi) = bit_range::<T>(range);
+        let field = low_mask::<T>(hi - lo);
+        if value & !field != T::ZERO {
+            field_value_fail(hi - lo);
+        }
+        if lo != hi {
+            let x = self.get() & !(field << lo);
+            self.set(x | (value << lo));
+        }
+    }
+
+    /// Returns bit `bit`, where bit 0 is the least significant bit.
+    ///
+    /// # Panics
+    /// Panics if `bit` is out of bounds for `T`.
+    #[inline]
+    #[track_caller]
+    pub fn get_bit(&self, bit: u32) -> bool {
+        if bit >= T::BITS {
+            bit_index_fail(bit, T::BITS);
+        }
+        self.get() & Lsb0::range_mask(bit, bit + 1) != T::ZERO
+    }
+
+    /// Sets bit `bit` to `value`, where bit 0 is the least significant bit.
+    ///
+    /// This reads and then writes the whole value.
+    ///
+    /// # Panics
+    /// Panics if `bit` is out of bounds for `T`.
+    #[inline]
+    #[track_caller]
+    pub fn set_bit(&mut self, bit: u32, value: bool) {
+        if bit >= T::BITS {
+            bit_index_fail(bit, T::BITS);
+        }
+        let mask: T = Lsb0::range_mask(bit, bit + 1);
+        let x = self.get();
+        self.set(if value { x | mask } else { x & !mask });
+    }
+
+    /// Reads the value once, passes `f` an existential reference to a local copy,
+    /// and writes the copy back once.
+    ///
+    /// This combines several field updates into a single write.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::Exists;
+    /// let mut x = 0u8;
+    /// Exists::from_mut(&mut x).modify(|bits| {
+    ///     bits.set_bit(7, true);
+    ///     bits.set_bits(0..3, 0b101);
+    /// });
+    /// assert_eq!(x, 0x85);
+    /// ```
+    #[inline]
+    pub fn modify(&mut self, f: impl FnOnce(&mut Exists<T>)) {
+        let mut x = self.get();
+        f(Exists::from_mut(&mut x));
+        self.set(x);
+    }
+
+    /// Like [`modify`](Exists::modify), but with a volatile read and write.
+    #[inline]
+    pub fn modify_volatile(&mut self, f: impl FnOnce(&mut Exists<T>)) {
+        let mut x = self.get_volatile();
+        f(Exists::from_mut(&mut x));
+        self.set_volatile(x);
+    }
+}
+
+/// Generates a wrapper over `&mut Exists<T>` with named accessors for bitfields of `T`.
+///
+/// Each field is declared as `getter, setter: bit;` for a `bool` field, or
+/// `getter, setter: lo..hi;` for a field of type `T` holding bits `[lo, hi)`.
+/// Bit 0 is the least significant bit. Each accessor reads, and each setter then
+/// writes, the whole integer.
+///
+/// The wrapper has a second type parameter choosing the [`Access`], which defaults
+/// to [`Normal`]. It is constructed with `new` or `new_volatile`, and also has
+/// `get` and `set` methods for the whole integer, and `into_inner`.
+///
+/// # Examples
+/// ```
+/// use exists_ref::{bitfields, Exists};
+///
+/// bitfields! {
+///     struct Control(u8) {
+///         enable, set_enable: 7;
+///         mode, set_mode: 0..2;
+///     }
+/// }
+///
+/// let mut x = 0x01u8;
+/// let mut control = Control::new(Exists::from_mut(&mut x));
+/// control.set_enable(true);
+/// assert_eq!(control.mode(), 1);
+/// assert_eq!(control.get(), 0x81);
+/// ```
+#[macro_export]
+macro_rules! bitfields {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($t:ty) {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $get:ident, $set:ident: $lo:literal $(.. $hi:literal)?;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name<'a, A = $crate::bitfield::Normal> {
+            bits: &'a mut $crate::Exists<$t>,
+            _access: ::core::marker::PhantomData<A>,
+        }
+
+        impl<'a> $name<'a> {
+            /// Wraps `bits`, accessing it with normal reads and writes.
+            #[inline]
+            $vis fn new(bits: &'a mut $crate::Exists<$t>) -> Self {
+                Self {
+                    bits,
+                    _access: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<'a> $name<'a, $crate::bitfield::Volatile> {
+            /// Wraps `bits`, accessing it with volatile reads and writes.
+            #[inline]
+            $vis fn new_volatile(bits: &'a mut $crate::Exists<$t>) -> Self {
+                Self {
+                    bits,
+                    _access: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl<'a, A: $crate::bitfield::Access> $name<'a, A> {
+            /// Reads the whole integer.
+            #[inline]
+            $vis fn get(&self) -> $t {
+                A::read(self.bits)
+            }
+
+            /// Writes the whole integer.
+            #[inline]
+            $vis fn set(&mut self, value: $t) {
+                A::write(self.bits, value)
+            }
+
+            /// Returns the wrapped existential reference.
+            #[inline]
+            $vis fn into_inner(self) -> &'a mut $crate::Exists<$t> {
+                self.bits
+            }
+
+            $(
+                $crate::__bitfield_accessors! {
+                    $(#[$field_meta])*
+                    $field_vis $get, $set: $t, $lo $(.. $hi)?
+                }
+            )*
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bitfield_accessors {
+    ($(#[$meta:meta])* $vis:vis $get:ident, $set:ident: $t:ty, $bit:literal) => {
+        $(#[$meta])*
+        #[inline]
+        $vis fn $get(&self) -> bool {
+            $crate::Exists::from_ref(&self.get()).get_bit($bit)
+        }
+
+        $(#[$meta])*
+        #[inline]
+        $vis fn $set(&mut self, value: bool) {
+            let mut x = self.get();
+            $crate::Exists::from_mut(&mut x).set_bit($bit, value);
+            self.set(x);
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis $get:ident, $set:ident: $t:ty, $lo:literal .. $hi:literal) => {
+        $(#[$meta])*
+        #[inline]
+        $vis fn $get(&self) -> $t {
+            $crate::Exists::from_ref(&self.get()).get_bits($lo..$hi)
+        }
+
+        $(#[$meta])*
+        ///
+        /// # Panics
+        /// Panics if `value` does not fit in the field.
+        #[inline]
+        #[track_caller]
+        $vis fn $set(&mut self, value: $t) {
+            let mut x = self.get();
+            $crate::Exists::from_mut(&mut x).set_bits($lo..$hi, value);
+            self.set(x);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    bitfields! {
+        struct Header(u32) {
+            version, set_version: 28..32;
+            flag, set_flag: 27;
+            len, set_len: 0..16;
+        }
+    }
+
+    #[test]
+    fn get_set_bits() {
+        let mut x = 0u32;
+        let e = Exists::from_mut(&mut x);
+        e.set_bits(8..16, 0xff);
+        e.set_bits(..4, 0x3);
+        e.set_bits(28.., 0x9);
+        e.set_bits(12..=13, 0);
+        e.set_bits(4..4, 0);
+        assert_eq!(e.get_bits(..), 0x9000_cf03);
+        assert_eq!(e.get_bits(8..12), 0xf);
+        assert_eq!(e.get_bits(32..), 0);
+        e.set_bit(31, false);
+        e.set_bit(1, false);
+        assert!(e.get_bit(0) && !e.get_bit(1) && !e.get_bit(31));
+        assert_eq!(x, 0x1000_cf01);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit range 4..9 out of bounds for a 8-bit integer")]
+    fn bit_range_out_of_bounds() {
+        Exists::from_ref(&0u8).get_bits(4..9);
+    }
+
+    #[test]
+    #[should_panic(expected = "bit 16 out of bounds for a 16-bit integer")]
+    fn bit_out_of_bounds() {
+        Exists::from_mut(&mut 0u16).set_bit(16, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "value does not fit in a 3-bit field")]
+    fn value_too_wide() {
+        Exists::from_mut(&mut 0u64).set_bits(4..7, 8);
+    }
+
+    #[test]
+    fn modify() {
+        let mut x = 0xffu8;
+        let [a, b] = Exists::from_mut(&mut x).copy_mut();
+        a.modify(|bits| {
+            bits.set_bits(0..4, 0);
+            // Not visible until `modify` returns.
+            assert_eq!(b.get(), 0xff);
+        });
+        b.modify_volatile(|bits| bits.set_bit(7, false));
+        assert_eq!(x, 0x70);
+    }
+
+    #[test]
+    fn wrapper() {
+        let mut x = 0u32;
+        let mut h = Header::new(Exists::from_mut(&mut x));
+        h.set_version(4);
+        h.set_flag(true);
+        h.set_len(1500);
+        assert_eq!((h.version(), h.flag(), h.len()), (4, true, 1500));
+        h.set_flag(false);
+        assert_eq!(h.get(), 0x4000_05dc);
+        let e = h.into_inner();
+        let h = Header::new_volatile(e);
+        assert_eq!(h.len(), 1500);
+    }
+
+    #[test]
+    #[should_panic(expected = "value does not fit in a 4-bit field")]
+    fn wrapper_value_too_wide() {
+        Header::new(Exists::from_mut(&mut 0)).set_version(16);
+    }
+}
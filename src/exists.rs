@@ -14,7 +14,9 @@
 
 use core::cell::{Cell, UnsafeCell};
 use core::marker::PhantomData;
-use core::ptr;
+use core::{mem, ptr};
+
+use crate::SliceExists;
 
 /// A ZST marker that indicates a valid `T` is accessible at this location.
 ///
@@ -201,7 +203,9 @@ impl<T> Exists<T> {
     /// }
     /// assert_eq!(x, 0);
     /// ```
+    #[allow(clippy::mut_from_ref, invalid_reference_casting)]
     pub unsafe fn assume_mut(&self) -> &mut Self {
+        // `Exists<T>` is a ZST, so the `&mut Self` covers no bytes and cannot alias `&self`.
         &mut *(self as *const Self as *mut Self)
     }
 
@@ -234,6 +238,138 @@ impl<T> Exists<T> {
         self as *mut Self as *mut T
     }
 
+    /// Returns an existential reference to the value `count` elements after this one.
+    ///
+    /// This mirrors [`pointer::add`], but keeps the result existential.
+    ///
+    /// # Safety
+    /// The resulting address must be in bounds of the same allocated object as `self`,
+    /// and meet the requirements of [`Exists::from_ptr`] for the returned lifetime.
+    ///
+    /// # Example
+    /// ```
+    /// # use exists_ref::Exists;
+    /// let x = [10u32, 20, 30];
+    /// let a: &Exists<u32> = Exists::from_ref(&x[0]);
+    /// assert_eq!(unsafe { a.add(2) }.get(), 30);
+    /// ```
+    ///
+    /// [`pointer::add`]: https://doc.rust-lang.org/std/primitive.pointer.html#method.add
+    #[inline]
+    pub unsafe fn add(&self, count: usize) -> &Self {
+        Self::from_ptr(self.as_ptr().add(count))
+    }
+
+    /// Returns a mutable existential reference to the value `count` elements after this one.
+    ///
+    /// # Safety
+    /// The resulting address must be in bounds of the same allocated object as `self`,
+    /// and meet the requirements of [`Exists::from_mut_ptr`] for the returned lifetime.
+    #[inline]
+    pub unsafe fn add_mut(&mut self, count: usize) -> &mut Self {
+        Self::from_mut_ptr(self.as_mut_ptr().add(count))
+    }
+
+    /// Returns an existential reference to the value `count` elements away from this one.
+    ///
+    /// This mirrors [`pointer::offset`], but keeps the result existential.
+    ///
+    /// # Safety
+    /// The resulting address must be in bounds of the same allocated object as `self`,
+    /// and meet the requirements of [`Exists::from_ptr`] for the returned lifetime.
+    ///
+    /// [`pointer::offset`]: https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
+    #[inline]
+    pub unsafe fn offset(&self, count: isize) -> &Self {
+        Self::from_ptr(self.as_ptr().offset(count))
+    }
+
+    /// Returns a mutable existential reference to the value `count` elements away from this one.
+    ///
+    /// # Safety
+    /// The resulting address must be in bounds of the same allocated object as `self`,
+    /// and meet the requirements of [`Exists::from_mut_ptr`] for the returned lifetime.
+    #[inline]
+    pub unsafe fn offset_mut(&mut self, count: isize) -> &mut Self {
+        Self::from_mut_ptr(self.as_mut_ptr().offset(count))
+    }
+
+    /// Returns an existential reference to the value `count` bytes after this one.
+    ///
+    /// # Safety
+    /// The resulting address must be in bounds of the same allocated object as `self`,
+    /// and meet the requirements of [`Exists::from_ptr`] for the returned lifetime.
+    /// In particular, it must be properly aligned for `T`.
+    #[inline]
+    pub unsafe fn byte_add(&self, count: usize) -> &Self {
+        Self::from_ptr(self.as_ptr().byte_add(count))
+    }
+
+    /// Returns a mutable existential reference to the value `count` bytes after this one.
+    ///
+    /// # Safety
+    /// The resulting address must be in bounds of the same allocated object as `self`,
+    /// and meet the requirements of [`Exists::from_mut_ptr`] for the returned lifetime.
+    /// In particular, it must be properly aligned for `T`.
+    #[inline]
+    pub unsafe fn byte_add_mut(&mut self, count: usize) -> &mut Self {
+        Self::from_mut_ptr(self.as_mut_ptr().byte_add(count))
+    }
+
+    /// Returns the index of this element within `slice`, or `None` if it
+    /// does not point to one of its elements.
+    ///
+    /// This only compares addresses, so it is safe to call with any slice.
+    /// For a zero-sized `T`, every element shares an address, so this returns `Some(0)`
+    /// if `self` is at the start of a non-empty `slice`.
+    ///
+    /// # Example
+    /// ```
+    /// # use exists_ref::{Exists, SliceExists};
+    /// let x = [1u16, 2, 3];
+    /// let s = SliceExists::from_ref(&x);
+    /// assert_eq!(s[2].offset_from_within(s), Some(2));
+    /// assert_eq!(s[2].offset_from_within(&s[..2]), None);
+    /// assert_eq!(Exists::from_ref(&5u16).offset_from_within(s), None);
+    /// ```
+    #[inline]
+    pub fn offset_from_within(&self, slice: &SliceExists<T>) -> Option<usize> {
+        let size = mem::size_of::<T>();
+        let byte_offset = (self.as_ptr() as usize).checked_sub(slice.as_ptr() as usize)?;
+        if size == 0 {
+            return (byte_offset == 0 && !slice.is_empty()).then_some(0);
+        }
+        let index = byte_offset / size;
+        (byte_offset % size == 0 && index < slice.len()).then_some(index)
+    }
+
+    /// Returns `true` if both references point to the same address.
+    ///
+    /// This compares the references themselves, not the values they point to.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+
+    /// Returns `true` if the two `T`s pointed to have at least one byte in common.
+    ///
+    /// References to a zero-sized `T` never overlap anything.
+    ///
+    /// # Example
+    /// ```
+    /// # use exists_ref::Exists;
+    /// let pairs = [[1u8, 2], [3, 4]];
+    /// let a: &Exists<[u8; 2]> = Exists::from_ref(&pairs[0]);
+    /// let b = unsafe { a.byte_add(1) };
+    /// assert!(a.overlaps(b));
+    /// assert!(!a.overlaps(unsafe { a.add(1) }));
+    /// ```
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (a, b) = (self.as_ptr() as usize, other.as_ptr() as usize);
+        a.abs_diff(b) < mem::size_of::<T>()
+    }
+
     /// Returns a shared reference that this `Exists<T>` points to.
     ///
     /// # Safety
@@ -336,6 +472,18 @@ impl<T: Copy> Exists<T> {
     pub fn set(&mut self, src: T) {
         unsafe { self.as_mut_ptr().write(src) }
     }
+
+    /// Gets the value at the address of `&self` with a volatile read.
+    /// Equivalent to [`ptr::read_volatile`].
+    pub fn get_volatile(&self) -> T {
+        unsafe { self.as_ptr().read_volatile() }
+    }
+
+    /// Sets a value at the address of `&mut self` with a volatile write.
+    /// Equivalent to [`ptr::write_volatile`].
+    pub fn set_volatile(&mut self, src: T) {
+        unsafe { self.as_mut_ptr().write_volatile(src) }
+    }
 }
 
 impl<T: Default> Exists<T> {
@@ -345,6 +493,38 @@ impl<T: Default> Exists<T> {
     }
 }
 
+macro_rules! impl_endian {
+    ($($t:ty)*) => {$(
+        impl Exists<$t> {
+            /// Gets the value at the address of `&self`, stored in little-endian byte order.
+            #[inline]
+            pub fn get_le(&self) -> $t {
+                <$t>::from_le_bytes(self.get().to_ne_bytes())
+            }
+
+            /// Gets the value at the address of `&self`, stored in big-endian byte order.
+            #[inline]
+            pub fn get_be(&self) -> $t {
+                <$t>::from_be_bytes(self.get().to_ne_bytes())
+            }
+
+            /// Sets a value at the address of `&mut self`, in little-endian byte order.
+            #[inline]
+            pub fn set_le(&mut self, src: $t) {
+                self.set(<$t>::from_ne_bytes(src.to_le_bytes()))
+            }
+
+            /// Sets a value at the address of `&mut self`, in big-endian byte order.
+            #[inline]
+            pub fn set_be(&mut self, src: $t) {
+                self.set(<$t>::from_ne_bytes(src.to_be_bytes()))
+            }
+        }
+    )*};
+}
+
+impl_endian!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
 impl<'a, T: 'a> From<&'a T> for &'a Exists<T> {
     /// Constructs an existential reference from a shared reference.
     fn from(item: &'a T) -> &'a Exists<T> {
@@ -485,5 +665,72 @@ mod tests {
         assert_eq!(x, 30);
     }
 
+    #[test]
+    fn pointer_arithmetic() {
+        let mut x = [1u32, 2, 3, 4];
+        let s = SliceExists::from_mut(&mut x);
+        let e = &mut s[1];
+        unsafe {
+            assert_eq!(e.add(2).get(), 4);
+            assert_eq!(e.offset(-1).get(), 1);
+            assert_eq!(e.byte_add(4).get(), 3);
+            e.offset_mut(-1).set(10);
+            e.add_mut(1).set(30);
+            e.byte_add_mut(8).set(40);
+        }
+        assert_eq!(x, [10, 2, 30, 40]);
+    }
+
+    #[test]
+    fn offset_from_within() {
+        let x = [0u32; 4];
+        let s = SliceExists::from_ref(&x);
+        for i in 0..4 {
+            assert_eq!(s[i].offset_from_within(s), Some(i));
+            assert_eq!(s[i].offset_from_within(&s[1..]), i.checked_sub(1));
+        }
+        // `[u8; 2]` has alignment 1, so a valid handle can straddle two elements.
+        let pairs = [[1u8, 2], [3, 4], [5, 6]];
+        let straddling =
+            unsafe { Exists::<[u8; 2]>::from_ptr(pairs.as_ptr().cast::<u8>().add(1).cast()) };
+        assert_eq!(straddling.get(), [2, 3]);
+        assert_eq!(
+            straddling.offset_from_within(SliceExists::from_ref(&pairs)),
+            None
+        );
+
+        let zsts = [(); 3];
+        let z = SliceExists::from_ref(&zsts);
+        assert_eq!(z[2].offset_from_within(z), Some(0));
+        assert_eq!(z[0].offset_from_within(&z[..0]), None);
+    }
+
+    #[test]
+    fn from_range() {
+        let mut x = [1u8, 2, 3, 4];
+        let s = SliceExists::from_mut(&mut x);
+        let [a, b] = s.copy_mut();
+        let end: &Exists<u8> = unsafe { Exists::from_ptr(b.as_ptr().add(4)) };
+        let tail = unsafe { SliceExists::from_range_mut(&mut a[2], end) };
+        assert_eq!(tail.len(), 2);
+        tail.fill(0);
+        let empty = unsafe { SliceExists::from_range(&b[1], &b[1]) };
+        assert!(empty.is_empty());
+        assert_eq!(x, [1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn endian() {
+        let mut x = 0u32;
+        Exists::from_mut(&mut x).set_be(0x0102_0304);
+        assert_eq!(x.to_ne_bytes(), [1, 2, 3, 4]);
+        assert_eq!(Exists::from_ref(&x).get_be(), 0x0102_0304);
+        assert_eq!(Exists::from_ref(&x).get_le(), 0x0403_0201);
+        let mut f = 0f64;
+        Exists::from_mut(&mut f).set_le(-0.5);
+        assert_eq!(f.to_ne_bytes(), (-0.5f64).to_le_bytes());
+        assert_eq!(Exists::from_ref(&f).get_le(), -0.5);
+    }
+
     // TODO: more rigorous testing
 }
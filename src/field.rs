@@ -0,0 +1,274 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Field projection for existential references.
+//!
+//! A [`Field<S, F>`] names a field of type `F` within a struct `S`, and is created with
+//! the [`field!`](crate::field!) macro. It projects an `&Exists<S>` to an `&Exists<F>`,
+//! and an `&SliceExists<S>` to a strided view of that field in every element, without
+//! reading anything. Both compute the same addresses, so `s[i].field(f)` and
+//! `s.field(f)[i]` refer to the same value.
+//!
+//! # Examples
+//! ```
+//! use exists_ref::{field, field::Field, SliceExists};
+//!
+//! #[derive(Clone, Copy)]
+//! struct Particle {
+//!     position: [f32; 2],
+//!     velocity: [f32; 2],
+//! }
+//! const VELOCITY: Field<Particle, [f32; 2]> = field!(Particle, velocity);
+//!
+//! let mut ps = [Particle { position: [0.0; 2], velocity: [1.0, 2.0] }; 4];
+//! let s = SliceExists::from_mut(&mut ps);
+//! s.field_mut(VELOCITY).fill([0.5, 0.5]);
+//! s[2].field_mut(VELOCITY).set([3.0, 3.0]);
+//! assert!(s.field(VELOCITY)[2].as_ptr() == s[2].field(VELOCITY).as_ptr());
+//! assert_eq!(ps[2].velocity, [3.0, 3.0]);
+//! assert_eq!(ps[3].velocity, [0.5, 0.5]);
+//! ```
+
+use core::marker::PhantomData;
+use core::mem;
+
+use crate::slice::{StridedSliceExists, StridedSliceExistsMut};
+use crate::{Exists, SliceExists};
+
+/// A field of type `F` within a struct `S`, identified by its byte offset.
+///
+/// Created by the [`field!`](crate::field!) macro.
+pub struct Field<S, F> {
+    offset: usize,
+    _phantom: PhantomData<fn(*const S) -> *const F>,
+}
+
+impl<S, F> Field<S, F> {
+    /// Constructs a field from its byte offset within `S`.
+    ///
+    /// # Safety
+    /// `offset` must be the offset of a field of type `F` within `S`, such as one
+    /// returned by [`core::mem::offset_of!`], and that field must be aligned for `F`
+    /// in every `S`. This is not the case for some fields of `#[repr(packed)]` structs.
+    #[inline]
+    pub const unsafe fn new_unchecked(offset: usize) -> Self {
+        Self {
+            offset,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Used by [`field!`](crate::field!). `_project` is never called, and only
+    /// ties the field's type to `F`.
+    ///
+    /// # Safety
+    /// `offset` must be the offset of the field that `_project` projects to.
+    #[doc(hidden)]
+    #[inline]
+    #[track_caller]
+    pub const unsafe fn __from_offset_of(
+        offset: usize,
+        _project: fn(*const S) -> *const F,
+    ) -> Self {
+        assert!(
+            mem::align_of::<F>() <= mem::align_of::<S>()
+                && offset.is_multiple_of(mem::align_of::<F>()),
+            "field is not aligned for its type"
+        );
+        Self::new_unchecked(offset)
+    }
+
+    /// Returns the offset of the field within `S`, in bytes.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<S, F> Clone for Field<S, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, F> Copy for Field<S, F> {}
+
+/// Creates a [`Field`] for a field path within a struct type.
+///
+/// This can be used in constants. It fails to compile if the path is not a field
+/// of the struct, and panics (at compile time, in a constant) if the field may be
+/// unaligned, as in some `#[repr(packed)]` structs.
+///
+/// # Examples
+/// ```
+/// use exists_ref::{field, Exists};
+///
+/// struct Inner { a: u8, b: u32 }
+/// struct Outer { x: u16, inner: Inner }
+///
+/// let o = Outer { x: 1, inner: Inner { a: 2, b: 3 } };
+/// let e = Exists::from_ref(&o);
+/// assert_eq!(e.field(field!(Outer, inner.b)).get(), 3);
+/// ```
+#[macro_export]
+macro_rules! field {
+    ($S:ty, $($field:tt)+) => {{
+        let project = |s: *const $S| unsafe { ::core::ptr::addr_of!((*s).$($field)+) };
+        // Safety: `offset_of!` and `project` refer to the same field path.
+        unsafe {
+            $crate::field::Field::__from_offset_of(
+                ::core::mem::offset_of!($S, $($field)+),
+                project,
+            )
+        }
+    }};
+}
+
+impl<S> Exists<S> {
+    /// Returns an existential reference to a field of the pointed-to struct.
+    ///
+    /// This does not perform any reads.
+    #[inline]
+    pub fn field<F>(&self, field: Field<S, F>) -> &Exists<F> {
+        // Safety: `field` is a properly aligned field of the `S` that `self` points to.
+        unsafe { Exists::from_ptr(self.as_ptr().byte_add(field.offset) as *const F) }
+    }
+
+    /// Returns a mutable existential reference to a field of the pointed-to struct.
+    ///
+    /// This does not perform any reads or writes.
+    #[inline]
+    pub fn field_mut<F>(&mut self, field: Field<S, F>) -> &mut Exists<F> {
+        // Safety: `field` is a properly aligned field of the `S` that `self` points to.
+        unsafe { Exists::from_mut_ptr(self.as_mut_ptr().byte_add(field.offset) as *mut F) }
+    }
+}
+
+impl<S> SliceExists<S> {
+    /// Returns a strided view of a field in every element of the slice.
+    ///
+    /// This does not perform any reads.
+    #[inline]
+    pub fn field<F>(&self, field: Field<S, F>) -> StridedSliceExists<'_, F> {
+        // Safety: `field` is a properly aligned field of `S`.
+        unsafe { self.project(field.offset) }
+    }
+
+    /// Returns a mutable strided view of a field in every element of the slice.
+    ///
+    /// This does not perform any reads or writes.
+    #[inline]
+    pub fn field_mut<F>(&mut self, field: Field<S, F>) -> StridedSliceExistsMut<'_, F> {
+        // Safety: `field` is a properly aligned field of `S`.
+        unsafe { self.project_mut(field.offset) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Clone, Copy)]
+    struct Particle {
+        position: Vec2,
+        mass: u16,
+        tag: (u8, u8),
+    }
+
+    const MASS: Field<Particle, u16> = field!(Particle, mass);
+
+    fn particles() -> [Particle; 3] {
+        [0u8, 1, 2].map(|i| Particle {
+            position: Vec2 {
+                x: i.into(),
+                y: -f32::from(i),
+            },
+            mass: 10 * u16::from(i),
+            tag: (i, 0),
+        })
+    }
+
+    #[test]
+    fn same_address() {
+        let ps = particles();
+        let s = SliceExists::from_ref(&ps);
+        let ys = s.field(field!(Particle, position.y));
+        for i in 0..3 {
+            assert!(ys[i].ptr_eq(s[i].field(field!(Particle, position.y))));
+            assert_eq!(ys[i].as_ptr(), &ps[i].position.y as *const f32);
+        }
+        assert_eq!(ys.iter().map(|e| e.get()).sum::<f32>(), -3.0);
+    }
+
+    #[test]
+    fn write_fields() {
+        let mut ps = particles();
+        let s = SliceExists::from_mut(&mut ps);
+        s.field_mut(MASS).fill(7);
+        s.field_mut(field!(Particle, tag.1)).fill(9);
+        s[1].field_mut(field!(Particle, position))
+            .set(Vec2 { x: 5.0, y: 6.0 });
+        assert!(ps.iter().all(|p| p.mass == 7 && p.tag.1 == 9));
+        assert_eq!((ps[1].position.x, ps[1].position.y), (5.0, 6.0));
+        assert_eq!(ps.map(|p| p.tag.0), [0, 1, 2]);
+    }
+
+    #[test]
+    fn aliasing_field_views() {
+        let mut ps = particles();
+        let [a, b] = SliceExists::from_mut(&mut ps).copy_mut();
+        let mut xs = a.field_mut(field!(Particle, position.x));
+        let ys = b.field(field!(Particle, position.y));
+        for (x, y) in xs.iter_mut().zip(ys.iter()) {
+            let sum = x.get() + y.get();
+            x.set(sum);
+        }
+        assert!(ps.iter().all(|p| p.position.x == 0.0));
+    }
+
+    #[test]
+    fn empty_slice() {
+        let mut ps = particles();
+        let s = SliceExists::from_mut(&mut ps);
+        assert!(s[3..].field(MASS).is_empty());
+        assert!(s[3..].field_mut(field!(Particle, tag.1)).is_empty());
+        let empty = <&SliceExists<Particle>>::default();
+        assert_eq!(empty.field(field!(Particle, position.y)).iter().count(), 0);
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(MASS.offset(), mem::offset_of!(Particle, mass));
+        let tag = field!(Particle, tag.1);
+        assert_eq!(tag.offset(), mem::offset_of!(Particle, tag.1));
+    }
+
+    #[test]
+    #[should_panic(expected = "field is not aligned for its type")]
+    fn packed() {
+        #[repr(C, packed)]
+        struct Packed {
+            _a: u8,
+            b: u32,
+        }
+        let _ = field!(Packed, b);
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reading into and writing from existential byte slices with [`std::io`].
+//!
+//! [`Read`] and [`Write`] take `&mut [u8]` and `&[u8]`, which cannot soundly be made
+//! from a `SliceExists<u8>` that other existential references may access. By default,
+//! these functions instead stage the bytes through a bounce buffer of at most
+//! [`BOUNCE_LEN`] bytes on the stack, and so may transfer fewer bytes per call than
+//! requested. The `_unchecked` variants lend the reader or writer a real slice, when
+//! the caller can vouch that nothing else accesses the bytes during the call.
+//!
+//! # Examples
+//! ```
+//! use exists_ref::{io, SliceExists};
+//!
+//! let mut buf = [0u8; 8];
+//! let [a, b] = SliceExists::from_mut(&mut buf).copy_mut();
+//! io::read_exact_into(&mut &b"headbody"[..], a).unwrap();
+//!
+//! let mut out = Vec::new();
+//! io::write_vectored_from(&mut out, &[&b[4..], &b[..4]]).unwrap();
+//! assert_eq!(out, b"bodyhead");
+//! ```
+
+use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+use std::vec::Vec;
+
+use crate::SliceExists;
+
+/// The maximum number of bytes staged through the bounce buffer in each call.
+pub const BOUNCE_LEN: usize = 8 * 1024;
+
+/// The length of the bounce buffer for short transfers.
+const SHORT_BOUNCE_LEN: usize = 256;
+
+/// Calls `f` with a zeroed bounce buffer of `len.min(BOUNCE_LEN)` bytes.
+///
+/// Short transfers use a smaller array, so they do not zero all [`BOUNCE_LEN`] bytes.
+#[inline]
+fn with_bounce<U>(len: usize, f: impl FnOnce(&mut [u8]) -> U) -> U {
+    if len <= SHORT_BOUNCE_LEN {
+        f(&mut [0; SHORT_BOUNCE_LEN][..len])
+    } else {
+        f(&mut [0; BOUNCE_LEN][..len.min(BOUNCE_LEN)])
+    }
+}
+
+/// Checks the count returned by [`Read::read`] into a buffer of `len` bytes.
+///
+/// A reader that claims to have read more bytes than fit is an error, not a panic.
+#[inline]
+fn check_read(n: usize, len: usize) -> io::Result<usize> {
+    if n > len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "reader returned more bytes than the buffer holds",
+        ));
+    }
+    Ok(n)
+}
+
+/// Reads some bytes from `reader` into the start of `dst`, and returns how many.
+///
+/// This makes one call to [`Read::read`] with a bounce buffer, so reads at most
+/// [`BOUNCE_LEN`] bytes.
+///
+/// # Errors
+/// Returns an [`InvalidData`](io::ErrorKind::InvalidData) error if `reader` claims
+/// to have read more bytes than it was given room for.
+pub fn read_into<R: Read + ?Sized>(reader: &mut R, dst: &mut SliceExists<u8>) -> io::Result<usize> {
+    with_bounce(dst.len(), |bounce| {
+        let n = check_read(reader.read(bounce)?, bounce.len())?;
+        dst[..n].copy_from(SliceExists::from_ref(&bounce[..n]));
+        Ok(n)
+    })
+}
+
+/// Reads exactly enough bytes from `reader` to fill `dst`.
+///
+/// This calls [`Read::read_exact`] once for each [`BOUNCE_LEN`] bytes. On error,
+/// some prefix of `dst` may have been written.
+pub fn read_exact_into<R: Read + ?Sized>(
+    reader: &mut R,
+    dst: &mut SliceExists<u8>,
+) -> io::Result<()> {
+    with_bounce(dst.len(), |bounce| {
+        for chunk in dst.chunks_mut(BOUNCE_LEN) {
+            let bounce = &mut bounce[..chunk.len()];
+            reader.read_exact(bounce)?;
+            chunk.copy_from(SliceExists::from_ref(bounce));
+        }
+        Ok(())
+    })
+}
+
+/// Writes some bytes from the start of `src` to `writer`, and returns how many.
+///
+/// This makes one call to [`Write::write`] with a bounce buffer, so writes at most
+/// [`BOUNCE_LEN`] bytes.
+pub fn write_from<W: Write + ?Sized>(writer: &mut W, src: &SliceExists<u8>) -> io::Result<usize> {
+    with_bounce(src.len(), |bounce| {
+        let n = bounce.len();
+        SliceExists::from_mut(&mut *bounce).copy_from(&src[..n]);
+        writer.write(bounce)
+    })
+}
+
+/// Writes all of `src` to `writer`.
+///
+/// This calls [`Write::write_all`] once for each [`BOUNCE_LEN`] bytes.
+pub fn write_all_from<W: Write + ?Sized>(writer: &mut W, src: &SliceExists<u8>) -> io::Result<()> {
+    with_bounce(src.len(), |bounce| {
+        for chunk in src.chunks(BOUNCE_LEN) {
+            let bounce = &mut bounce[..chunk.len()];
+            SliceExists::from_mut(&mut *bounce).copy_from(chunk);
+            writer.write_all(bounce)?;
+        }
+        Ok(())
+    })
+}
+
+/// Reads some bytes from `reader` into `dsts` in order, and returns how many.
+///
+/// This makes one call to [`Read::read`] with a bounce buffer, so reads at most
+/// [`BOUNCE_LEN`] bytes. The slices in `dsts` may overlap, in which case later
+/// slices overwrite earlier ones.
+///
+/// # Errors
+/// Returns an [`InvalidData`](io::ErrorKind::InvalidData) error if `reader` claims
+/// to have read more bytes than it was given room for.
+pub fn read_vectored_into<R: Read + ?Sized>(
+    reader: &mut R,
+    dsts: &mut [&mut SliceExists<u8>],
+) -> io::Result<usize> {
+    // The slices may alias, so their lengths may sum to more than `usize::MAX`.
+    let total = dsts.iter().map(|d| d.len()).fold(0, usize::saturating_add);
+    with_bounce(total, |bounce| {
+        let n = check_read(reader.read(bounce)?, bounce.len())?;
+        let mut rest = &bounce[..n];
+        for dst in dsts {
+            let (head, tail) = rest.split_at(dst.len().min(rest.len()));
+            dst[..head.len()].copy_from(SliceExists::from_ref(head));
+            rest = tail;
+        }
+        Ok(n)
+    })
+}
+
+/// Writes some bytes from `srcs` in order to `writer`, and returns how many.
+///
+/// This makes one call to [`Write::write`] with a bounce buffer, so writes at most
+/// [`BOUNCE_LEN`] bytes.
+pub fn write_vectored_from<W: Write + ?Sized>(
+    writer: &mut W,
+    srcs: &[&SliceExists<u8>],
+) -> io::Result<usize> {
+    let total = srcs.iter().map(|s| s.len()).fold(0, usize::saturating_add);
+    with_bounce(total, |bounce| {
+        let mut n = 0;
+        for src in srcs {
+            let len = src.len().min(bounce.len() - n);
+            SliceExists::from_mut(&mut bounce[n..n + len]).copy_from(&src[..len]);
+            n += len;
+        }
+        writer.write(bounce)
+    })
+}
+
+/// Reads some bytes from `reader` directly into the start of `dst`, and returns how many.
+///
+/// # Safety
+/// For the duration of the call, `dst` must meet the requirements of
+/// [`SliceExists::as_mut_unchecked`]: its bytes must not be accessed except through
+/// the slice lent to `reader`.
+pub unsafe fn read_into_unchecked<R: Read + ?Sized>(
+    reader: &mut R,
+    dst: &mut SliceExists<u8>,
+) -> io::Result<usize> {
+    reader.read(dst.as_mut_unchecked())
+}
+
+/// Writes some bytes from the start of `src` directly to `writer`, and returns how many.
+///
+/// # Safety
+/// For the duration of the call, `src` must meet the requirements of
+/// [`SliceExists::as_ref_unchecked`]: its bytes must not be written.
+pub unsafe fn write_from_unchecked<W: Write + ?Sized>(
+    writer: &mut W,
+    src: &SliceExists<u8>,
+) -> io::Result<usize> {
+    writer.write(src.as_ref_unchecked())
+}
+
+/// Reads some bytes from `reader` directly into `dsts` with [`Read::read_vectored`],
+/// and returns how many.
+///
+/// This allocates the list of slices.
+///
+/// # Safety
+/// For the duration of the call, each slice in `dsts` must meet the requirements of
+/// [`SliceExists::as_mut_unchecked`]. In particular, the slices must not overlap.
+pub unsafe fn read_vectored_into_unchecked<R: Read + ?Sized>(
+    reader: &mut R,
+    dsts: &mut [&mut SliceExists<u8>],
+) -> io::Result<usize> {
+    let mut bufs: Vec<_> = dsts
+        .iter_mut()
+        .map(|d| IoSliceMut::new(d.as_mut_unchecked()))
+        .collect();
+    reader.read_vectored(&mut bufs)
+}
+
+/// Writes some bytes from `srcs` directly to `writer` with [`Write::write_vectored`],
+/// and returns how many.
+///
+/// This allocates the list of slices.
+///
+/// # Safety
+/// For the duration of the call, each slice in `srcs` must meet the requirements of
+/// [`SliceExists::as_ref_unchecked`].
+pub unsafe fn write_vectored_from_unchecked<W: Write + ?Sized>(
+    writer: &mut W,
+    srcs: &[&SliceExists<u8>],
+) -> io::Result<usize> {
+    let bufs: Vec<_> = srcs
+        .iter()
+        .map(|s| IoSlice::new(s.as_ref_unchecked()))
+        .collect();
+    writer.write_vectored(&bufs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn bounce_limits() {
+        let data = pattern(BOUNCE_LEN + 10);
+        let mut buf = vec![0u8; BOUNCE_LEN + 20];
+        let dst = SliceExists::from_mut(&mut buf);
+        assert_eq!(read_into(&mut &data[..], dst).unwrap(), BOUNCE_LEN);
+        let mut out = Vec::new();
+        assert_eq!(write_from(&mut out, dst).unwrap(), BOUNCE_LEN);
+        assert_eq!(out, data[..BOUNCE_LEN]);
+    }
+
+    #[test]
+    fn exact_and_all() {
+        let data = pattern(2 * BOUNCE_LEN + 3);
+        let mut buf = vec![0u8; data.len()];
+        let dst = SliceExists::from_mut(&mut buf);
+        read_exact_into(&mut &data[..], dst).unwrap();
+        let mut out = Vec::new();
+        write_all_from(&mut out, dst).unwrap();
+        assert_eq!((&buf, &out), (&data, &data));
+
+        let err = read_exact_into(&mut &data[..4], SliceExists::from_mut(&mut [0; 5]));
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn vectored() {
+        let mut buf = [0u8; 6];
+        let [s, t] = SliceExists::from_mut(&mut buf).copy_mut();
+        let (a, b) = s.split_at_mut(2);
+        let n = read_vectored_into(&mut &b"abcde"[..], &mut [a, &mut t[..0], b]).unwrap();
+        assert_eq!(n, 5);
+        assert_eq!(&buf, b"abcde\0");
+
+        let s = SliceExists::from_ref(b"abcdef");
+        let mut out = Vec::new();
+        assert_eq!(
+            write_vectored_from(&mut out, &[&s[4..], &s[..2]]).unwrap(),
+            4
+        );
+        assert_eq!(out, b"efab");
+    }
+
+    /// A reader that claims to have read more bytes than it was given room for.
+    struct Overread;
+
+    impl Read for Overread {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            Ok(buf.len() + 1)
+        }
+    }
+
+    #[test]
+    fn overread_is_an_error() {
+        let mut buf = [0u8; 4];
+        let s = SliceExists::from_mut(&mut buf);
+        let err = read_into(&mut Overread, s).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let (a, b) = s.split_at_mut(1);
+        let err = read_vectored_into(&mut Overread, &mut [a, b]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(buf, [0; 4]);
+    }
+
+    #[test]
+    fn short_and_long_transfers() {
+        for len in [0, 1, SHORT_BOUNCE_LEN, SHORT_BOUNCE_LEN + 1, BOUNCE_LEN] {
+            let data = pattern(len);
+            let mut buf = vec![0u8; len];
+            let dst = SliceExists::from_mut(&mut buf);
+            assert_eq!(read_into(&mut &data[..], dst).unwrap(), len);
+            let mut out = Vec::new();
+            assert_eq!(write_vectored_from(&mut out, &[dst]).unwrap(), len);
+            assert_eq!((&buf, &out), (&data, &data));
+        }
+    }
+
+    #[test]
+    fn unchecked() {
+        let mut buf = [0u8; 4];
+        let s = SliceExists::from_mut(&mut buf);
+        assert_eq!(
+            unsafe { read_into_unchecked(&mut &b"xyz"[..], s) }.unwrap(),
+            3
+        );
+        let mut out = Vec::new();
+        assert_eq!(
+            unsafe { write_from_unchecked(&mut out, &s[..2]) }.unwrap(),
+            2
+        );
+        let (a, b) = s.split_at_mut(1);
+        let n = unsafe { read_vectored_into_unchecked(&mut &b"1234"[..], &mut [a, b]) };
+        assert_eq!(n.unwrap(), 4);
+        let n = unsafe { write_vectored_from_unchecked(&mut out, &[&s[3..], &s[..1]]) };
+        assert_eq!(n.unwrap(), 2);
+        assert_eq!((&buf, &out[..]), (b"1234", &b"xy41"[..]));
+    }
+}
@@ -121,8 +121,18 @@
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod bitfield;
 mod exists;
+pub mod field;
+#[cfg(feature = "std")]
+pub mod io;
 pub mod slice;
+pub mod view;
 
 pub use exists::Exists;
 pub use slice::SliceExists;
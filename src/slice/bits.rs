@@ -0,0 +1,713 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bit-level views over existential slices of unsigned integers.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{self, ControlFlow, Index, RangeBounds};
+
+use super::index::{self, IndexError, IndexErrorKind};
+use super::SliceExists;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type whose bits can be viewed with a [`BitSliceExists`].
+///
+/// This trait is sealed, and implemented for `u8`, `u16`, `u32`, `u64` and `usize`.
+pub trait BitStore:
+    sealed::Sealed
+    + Copy
+    + Eq
+    + ops::Not<Output = Self>
+    + ops::BitAnd<Output = Self>
+    + ops::BitOr<Output = Self>
+    + ops::BitXor<Output = Self>
+    + ops::Shl<u32, Output = Self>
+    + ops::Shr<u32, Output = Self>
+{
+    /// The number of bits in the type.
+    const BITS: u32;
+    #[doc(hidden)]
+    const ZERO: Self;
+    #[doc(hidden)]
+    const ONES: Self;
+    #[doc(hidden)]
+    fn count_ones(self) -> u32;
+    #[doc(hidden)]
+    fn leading_zeros(self) -> u32;
+    #[doc(hidden)]
+    fn trailing_zeros(self) -> u32;
+}
+
+macro_rules! impl_bit_store {
+    ($($t:ty)*) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl BitStore for $t {
+            const BITS: u32 = <$t>::BITS;
+            const ZERO: Self = 0;
+            const ONES: Self = <$t>::MAX;
+
+            #[inline]
+            fn count_ones(self) -> u32 {
+                self.count_ones()
+            }
+
+            #[inline]
+            fn leading_zeros(self) -> u32 {
+                self.leading_zeros()
+            }
+
+            #[inline]
+            fn trailing_zeros(self) -> u32 {
+                self.trailing_zeros()
+            }
+        }
+    )*};
+}
+
+impl_bit_store!(u8 u16 u32 u64 usize);
+
+/// The order of bits within each element of a [`BitSliceExists`].
+///
+/// This trait is sealed, and implemented for [`Lsb0`] and [`Msb0`].
+pub trait BitOrder: sealed::Sealed {
+    /// Returns the mask of in-element bit indices `[lo, hi)`, where `lo < hi <= T::BITS`.
+    #[doc(hidden)]
+    fn range_mask<T: BitStore>(lo: u32, hi: u32) -> T;
+
+    /// Returns the in-element index of the first set bit of `x`, which is non-zero.
+    #[doc(hidden)]
+    fn first_one<T: BitStore>(x: T) -> u32;
+}
+
+/// Bit index 0 of each element is its least significant bit.
+pub enum Lsb0 {}
+
+/// Bit index 0 of each element is its most significant bit.
+pub enum Msb0 {}
+
+impl sealed::Sealed for Lsb0 {}
+impl sealed::Sealed for Msb0 {}
+
+impl BitOrder for Lsb0 {
+    #[inline]
+    fn range_mask<T: BitStore>(lo: u32, hi: u32) -> T {
+        (T::ONES >> (T::BITS - (hi - lo))) << lo
+    }
+
+    #[inline]
+    fn first_one<T: BitStore>(x: T) -> u32 {
+        x.trailing_zeros()
+    }
+}
+
+impl BitOrder for Msb0 {
+    #[inline]
+    fn range_mask<T: BitStore>(lo: u32, hi: u32) -> T {
+        (T::ONES >> (T::BITS - (hi - lo))) << (T::BITS - hi)
+    }
+
+    #[inline]
+    fn first_one<T: BitStore>(x: T) -> u32 {
+        x.leading_zeros()
+    }
+}
+
+/// A view of the bits of an existential slice of unsigned integers.
+///
+/// Bit `i` of the view is bit `i % T::BITS` of element `i / T::BITS`, counting from
+/// the least significant bit with [`Lsb0`] or the most significant bit with [`Msb0`].
+/// A view made with [`range`](Self::range) may start partway through an element.
+///
+/// Created by [`SliceExists::as_bits`].
+///
+/// # Examples
+/// ```
+/// # use exists_ref::{slice::{Lsb0, Msb0}, SliceExists};
+/// let mut x = [0u8; 2];
+/// let s = SliceExists::from_mut(&mut x);
+/// s.as_bits_mut::<Lsb0>().range_mut(4..12).fill(true);
+/// s.as_bits_mut::<Msb0>().set(15, false);
+/// assert_eq!(x, [0xf0, 0x0e]);
+/// ```
+pub struct BitSliceExists<'a, T, O = Lsb0> {
+    ptr: *const T,
+    head: u32,
+    len: usize,
+    _phantom: PhantomData<(&'a SliceExists<T>, O)>,
+}
+
+/// A mutable view of the bits of an existential slice of unsigned integers.
+///
+/// Each write reads and writes back the whole element, so like `&mut SliceExists<T>`,
+/// mutable bit views may alias each other.
+///
+/// Created by [`SliceExists::as_bits_mut`].
+pub struct BitSliceExistsMut<'a, T, O = Lsb0> {
+    v: BitSliceExists<'a, T, O>,
+    _phantom: PhantomData<&'a mut SliceExists<T>>,
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn out_of_bounds_fail(index: usize, len: usize) -> ! {
+    panic!(
+        "{}",
+        IndexError::new(IndexErrorKind::OutOfBounds, index, len)
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn bit_len_fail(len: usize) -> ! {
+    panic!("slice of length {} has too many bits to index", len)
+}
+
+impl<T, O> Clone for BitSliceExists<'_, T, O> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, O> Copy for BitSliceExists<'_, T, O> {}
+
+impl<'a, T: BitStore, O: BitOrder> BitSliceExists<'a, T, O> {
+    /// Constructs a bit view from its raw parts.
+    ///
+    /// # Safety
+    /// `head` must be less than `T::BITS`, and every element holding one of the bits
+    /// `[head, head + len)` counting from `data` must meet the requirements of
+    /// [`Exists::from_ptr`](crate::Exists::from_ptr) for lifetime `'a`. All of these
+    /// elements must be within a single allocated object.
+    #[inline]
+    pub unsafe fn from_raw_parts(data: *const T, head: u32, len: usize) -> Self {
+        Self {
+            ptr: data,
+            head,
+            len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of bits in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a pointer to the element holding the first bit, and that bit's index
+    /// within the element.
+    #[inline]
+    pub fn as_raw_parts(&self) -> (*const T, u32) {
+        (self.ptr, self.head)
+    }
+
+    /// Returns the element holding bit `i`, and the mask of that bit within it.
+    ///
+    /// # Safety
+    /// `i` must be in bounds.
+    #[inline]
+    unsafe fn locate(&self, i: usize) -> (*const T, T) {
+        let bit = self.head as usize + i;
+        let b = (bit % T::BITS as usize) as u32;
+        (
+            self.ptr.add(bit / T::BITS as usize),
+            O::range_mask(b, b + 1),
+        )
+    }
+
+    /// Calls `f` with each element overlapping the view, the mask of the view's bits
+    /// within it, and the (wrapping) index in the view of the element's bit 0.
+    fn try_for_each_elem<B>(
+        &self,
+        mut f: impl FnMut(*const T, T, usize) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        let bits = T::BITS as usize;
+        let (mut p, mut lo, mut i) = (self.ptr, self.head, 0);
+        while i < self.len {
+            let n = (bits - lo as usize).min(self.len - i);
+            let hi = lo + n as u32;
+            // Bit `b` of this element is at index `i + (b - lo)` in the view.
+            f(p, O::range_mask(lo, hi), i.wrapping_sub(lo as usize))?;
+            i += n;
+            lo = 0;
+            // Past the end only once the loop is done, so it is never read.
+            p = p.wrapping_add(1);
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Returns bit `i`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if i < self.len {
+            // Safety: `i` is in bounds.
+            Some(unsafe { self.get_unchecked(i) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns bit `i`, without bounds checking.
+    ///
+    /// # Safety
+    /// `i` must be in bounds.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, i: usize) -> bool {
+        let (p, mask) = self.locate(i);
+        p.read() & mask != T::ZERO
+    }
+
+    /// Returns the view of the bits in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let range = index::range(range, self.len);
+        let bit = self.head as usize + range.start;
+        Self {
+            // May point one past the end when the range is empty.
+            ptr: self.ptr.wrapping_add(bit / T::BITS as usize),
+            head: (bit % T::BITS as usize) as u32,
+            len: range.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> usize {
+        let mut n = 0;
+        let _ = self.try_for_each_elem(|p, mask, _| {
+            // Safety: `p` holds bits of the view.
+            n += (unsafe { p.read() } & mask).count_ones() as usize;
+            ControlFlow::<()>::Continue(())
+        });
+        n
+    }
+
+    /// Returns the number of unset bits.
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Returns the index of the first set bit, or `None` if there are none.
+    ///
+    /// This reads one element at a time.
+    pub fn first_one(&self) -> Option<usize> {
+        self.find_first(|x| x)
+    }
+
+    /// Returns the index of the first unset bit, or `None` if there are none.
+    ///
+    /// This reads one element at a time.
+    pub fn first_zero(&self) -> Option<usize> {
+        self.find_first(|x| !x)
+    }
+
+    #[inline]
+    fn find_first(&self, map: impl Fn(T) -> T) -> Option<usize> {
+        self.try_for_each_elem(|p, mask, base| {
+            // Safety: `p` holds bits of the view.
+            let x = map(unsafe { p.read() }) & mask;
+            if x == T::ZERO {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(base.wrapping_add(O::first_one(x) as usize))
+            }
+        })
+        .break_value()
+    }
+
+    /// Returns an iterator over the bits of the view.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = bool> + 'a
+    where
+        O: 'a,
+    {
+        let v = *self;
+        // Safety: `i` is in bounds.
+        (0..self.len).map(move |i| unsafe { v.get_unchecked(i) })
+    }
+
+    /// Returns an iterator over the indices of the set bits, in increasing order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::{slice::Msb0, SliceExists};
+    /// let s = SliceExists::from_ref(&[0x81u8, 0x40]);
+    /// let ones: Vec<_> = s.as_bits::<Msb0>().iter_ones().collect();
+    /// assert_eq!(ones, [0, 7, 9]);
+    /// ```
+    #[inline]
+    pub fn iter_ones(&self) -> IterOnes<'a, T, O> {
+        IterOnes { v: *self, pos: 0 }
+    }
+}
+
+impl<T: BitStore, O: BitOrder> Index<usize> for BitSliceExists<'_, T, O> {
+    type Output = bool;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, i: usize) -> &bool {
+        match self.get(i) {
+            Some(true) => &true,
+            Some(false) => &false,
+            None => out_of_bounds_fail(i, self.len),
+        }
+    }
+}
+
+impl<'a, T: BitStore, O: BitOrder> BitSliceExistsMut<'a, T, O> {
+    /// Constructs a mutable bit view from a shared one.
+    ///
+    /// # Safety
+    /// The elements holding the bits of `v` must be valid for writes for lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_bits(v: BitSliceExists<'a, T, O>) -> Self {
+        Self {
+            v,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns a shared view of the same bits.
+    #[inline]
+    pub fn as_bits(&self) -> BitSliceExists<'_, T, O> {
+        self.v
+    }
+
+    /// Returns a shorter-lived mutable view of the same bits.
+    #[inline]
+    pub fn reborrow(&mut self) -> BitSliceExistsMut<'_, T, O> {
+        // Safety: the copy is only usable for as long as `self` is borrowed.
+        unsafe { BitSliceExistsMut::from_bits(self.v) }
+    }
+
+    /// Returns `N` mutable views of the same bits.
+    #[inline]
+    pub fn copy_mut<const N: usize>(&mut self) -> [BitSliceExistsMut<'_, T, O>; N] {
+        // Safety: each copy is only usable for as long as `self` is borrowed.
+        [self.v; N].map(|v| unsafe { BitSliceExistsMut::from_bits(v) })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.v.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.v.is_empty()
+    }
+
+    /// Returns bit `i`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<bool> {
+        self.v.get(i)
+    }
+
+    /// Applies `f` to the element holding bit `i` and the mask of that bit.
+    #[inline]
+    #[track_caller]
+    fn update(&mut self, i: usize, f: impl FnOnce(T, T) -> T) {
+        if i >= self.v.len {
+            out_of_bounds_fail(i, self.v.len);
+        }
+        // Safety: `i` is in bounds, and `self` is valid for writes.
+        unsafe {
+            let (p, mask) = self.v.locate(i);
+            let p = p as *mut T;
+            p.write(f(p.read(), mask));
+        }
+    }
+
+    /// Sets bit `i` to `value`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn set(&mut self, i: usize, value: bool) {
+        self.update(i, |x, mask| if value { x | mask } else { x & !mask });
+    }
+
+    /// Flips bit `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn toggle(&mut self, i: usize) {
+        self.update(i, |x, mask| x ^ mask);
+    }
+
+    /// Returns the mutable view of the bits in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> BitSliceExistsMut<'_, T, O> {
+        // Safety: the view is only usable for as long as `self` is borrowed.
+        unsafe { BitSliceExistsMut::from_bits(self.v.range(range)) }
+    }
+
+    /// Sets every bit to `value`.
+    ///
+    /// Elements entirely within the view are written without being read.
+    pub fn fill(&mut self, value: bool) {
+        let _ = self.v.try_for_each_elem(|p, mask, _| {
+            let p = p as *mut T;
+            // Safety: `p` holds bits of the view, and `self` is valid for writes.
+            unsafe {
+                if mask == T::ONES {
+                    p.write(if value { T::ONES } else { T::ZERO });
+                } else {
+                    let x = p.read();
+                    p.write(if value { x | mask } else { x & !mask });
+                }
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+
+    /// Returns the number of set bits.
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.v.count_ones()
+    }
+
+    /// Returns the number of unset bits.
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        self.v.count_zeros()
+    }
+
+    /// Returns the index of the first set bit, or `None` if there are none.
+    #[inline]
+    pub fn first_one(&self) -> Option<usize> {
+        self.v.first_one()
+    }
+
+    /// Returns the index of the first unset bit, or `None` if there are none.
+    #[inline]
+    pub fn first_zero(&self) -> Option<usize> {
+        self.v.first_zero()
+    }
+
+    /// Returns an iterator over the indices of the set bits, in increasing order.
+    ///
+    /// Bits are read as the iterator advances.
+    #[inline]
+    pub fn iter_ones(&self) -> IterOnes<'_, T, O> {
+        self.v.iter_ones()
+    }
+}
+
+impl<T: BitStore, O: BitOrder> Index<usize> for BitSliceExistsMut<'_, T, O> {
+    type Output = bool;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, i: usize) -> &bool {
+        &self.v[i]
+    }
+}
+
+/// An iterator over the indices of the set bits of a [`BitSliceExists`].
+///
+/// Created by [`BitSliceExists::iter_ones`].
+pub struct IterOnes<'a, T, O> {
+    v: BitSliceExists<'a, T, O>,
+    pos: usize,
+}
+
+impl<T, O> Clone for IterOnes<'_, T, O> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            v: self.v,
+            pos: self.pos,
+        }
+    }
+}
+
+impl<T: BitStore, O: BitOrder> Iterator for IterOnes<'_, T, O> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let i = self.v.first_one()?;
+        self.v = self.v.range(i + 1..);
+        let index = self.pos + i;
+        self.pos = index + 1;
+        Some(index)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.v.len))
+    }
+}
+
+impl<T: BitStore, O: BitOrder> FusedIterator for IterOnes<'_, T, O> {}
+
+impl<T: BitStore> SliceExists<T> {
+    /// Returns a view of the bits of the slice, in the order given by `O`.
+    ///
+    /// # Panics
+    /// Panics if the number of bits overflows a `usize`.
+    #[inline]
+    #[track_caller]
+    pub fn as_bits<O: BitOrder>(&self) -> BitSliceExists<'_, T, O> {
+        let len = match self.len().checked_mul(T::BITS as usize) {
+            Some(len) => len,
+            None => bit_len_fail(self.len()),
+        };
+        // Safety: the bits are those of the elements of `self`.
+        unsafe { BitSliceExists::from_raw_parts(self.as_ptr(), 0, len) }
+    }
+
+    /// Returns a mutable view of the bits of the slice, in the order given by `O`.
+    ///
+    /// # Panics
+    /// Panics if the number of bits overflows a `usize`.
+    #[inline]
+    #[track_caller]
+    pub fn as_bits_mut<O: BitOrder>(&mut self) -> BitSliceExistsMut<'_, T, O> {
+        let v = self.as_bits();
+        // Safety: `self` is valid for writes.
+        unsafe { BitSliceExistsMut::from_bits(v) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn orderings() {
+        let x = [0b0000_0110u8, 0x80];
+        let s = SliceExists::from_ref(&x);
+        let lsb = s.as_bits::<Lsb0>();
+        let msb = s.as_bits::<Msb0>();
+        assert_eq!(lsb.len(), 16);
+        assert_eq!(lsb.iter_ones().collect::<Vec<_>>(), [1, 2, 15]);
+        assert_eq!(msb.iter_ones().collect::<Vec<_>>(), [5, 6, 8]);
+        assert!(lsb[1] && !lsb[0] && msb[8]);
+        assert_eq!(lsb.get(16), None);
+    }
+
+    #[test]
+    fn set_toggle() {
+        let mut x = [0u32; 2];
+        let s = SliceExists::from_mut(&mut x);
+        let mut bits = s.as_bits_mut::<Lsb0>();
+        bits.set(0, true);
+        bits.set(33, true);
+        bits.toggle(34);
+        bits.toggle(0);
+        bits.set(33, false);
+        bits.set(63, true);
+        assert_eq!(x, [0, 0x8000_0004]);
+
+        let mut x = [0u16];
+        SliceExists::from_mut(&mut x)
+            .as_bits_mut::<Msb0>()
+            .set(0, true);
+        assert_eq!(x, [0x8000]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 6 but the index is 6")]
+    fn set_out_of_bounds() {
+        let mut x = [0u8];
+        SliceExists::from_mut(&mut x)
+            .as_bits_mut::<Lsb0>()
+            .range_mut(1..7)
+            .set(6, true);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut x = [0u8; 4];
+        let s = SliceExists::from_mut(&mut x);
+        let mut bits = s.as_bits_mut::<Lsb0>();
+        bits.range_mut(3..29).fill(true);
+        assert_eq!(bits.count_ones(), 26);
+        bits.range_mut(8..16).fill(false);
+        let sub = bits.as_bits().range(5..20);
+        assert_eq!((sub.len(), sub.count_ones(), sub.count_zeros()), (15, 7, 8));
+        assert_eq!(sub.first_zero(), Some(3));
+        assert_eq!(sub.range(3..).first_one(), Some(8));
+        assert_eq!(sub.range(15..).first_one(), None);
+        assert_eq!(x, [0xf8, 0x00, 0xff, 0x1f]);
+    }
+
+    #[test]
+    fn msb_ranges() {
+        let mut x = [0u64, 0];
+        let s = SliceExists::from_mut(&mut x);
+        let mut bits = s.as_bits_mut::<Msb0>();
+        bits.range_mut(60..68).fill(true);
+        assert_eq!(bits.first_one(), Some(60));
+        assert_eq!(bits.range_mut(62..).first_zero(), Some(6));
+        assert_eq!(bits.as_bits().iter().filter(|&b| b).count(), 8);
+        assert_eq!(x, [0xf, 0xf << 60]);
+    }
+
+    #[test]
+    fn aliasing_views() {
+        let mut x = [0usize; 2];
+        let s = SliceExists::from_mut(&mut x);
+        let mut bits = s.as_bits_mut::<Lsb0>();
+        let [mut a, b] = bits.copy_mut();
+        a.set(70, true);
+        assert_eq!(b.first_one(), Some(70));
+        assert_eq!(b.iter_ones().collect::<Vec<_>>(), [70]);
+    }
+
+    #[test]
+    fn empty() {
+        let s = <&SliceExists<u8>>::default().as_bits::<Lsb0>();
+        assert!(s.is_empty());
+        assert_eq!(
+            (s.first_one(), s.first_zero(), s.count_ones()),
+            (None, None, 0)
+        );
+        let mut x = [0u8; 2];
+        let mut bits = SliceExists::from_mut(&mut x).as_bits_mut::<Lsb0>();
+        bits.range_mut(16..).fill(true);
+        bits.range_mut(8..8).fill(true);
+        assert_eq!(x, [0, 0]);
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operations specific to existential byte slices.
+
+use core::mem;
+
+use crate::slice::{IndexError, IndexErrorKind, SliceExists};
+
+const WORD: usize = mem::size_of::<usize>();
+const LO: usize = usize::from_ne_bytes([0x01; WORD]);
+const HI: usize = usize::from_ne_bytes([0x80; WORD]);
+
+/// Returns `true` if any byte of `x` is zero.
+#[inline]
+fn contains_zero_byte(x: usize) -> bool {
+    x.wrapping_sub(LO) & !x & HI != 0
+}
+
+impl SliceExists<u8> {
+    /// Returns the index of the first occurrence of `byte`.
+    ///
+    /// This scans a word at a time with unaligned raw pointer reads.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let s = SliceExists::from_ref(b"hello, world");
+    /// assert_eq!(s.find(b'o'), Some(4));
+    /// assert_eq!(s.rfind(b'o'), Some(8));
+    /// assert_eq!(s.find(b'z'), None);
+    /// ```
+    pub fn find(&self, byte: u8) -> Option<usize> {
+        let p = self.as_ptr();
+        let len = self.len();
+        let repeated = LO * byte as usize;
+        let mut i = 0;
+        while i + WORD <= len {
+            // Safety: `i + WORD <= len`, so the word is in bounds.
+            let word = unsafe { p.add(i).cast::<usize>().read_unaligned() };
+            if contains_zero_byte(word ^ repeated) {
+                break;
+            }
+            i += WORD;
+        }
+        self[i..].position(|&b| b == byte).map(|j| i + j)
+    }
+
+    /// Returns the index of the last occurrence of `byte`.
+    ///
+    /// See [`find`](SliceExists::find).
+    pub fn rfind(&self, byte: u8) -> Option<usize> {
+        let p = self.as_ptr();
+        let repeated = LO * byte as usize;
+        let mut end = self.len();
+        while end >= WORD {
+            // Safety: `end - WORD..end` is in bounds.
+            let word = unsafe { p.add(end - WORD).cast::<usize>().read_unaligned() };
+            if contains_zero_byte(word ^ repeated) {
+                break;
+            }
+            end -= WORD;
+        }
+        self[..end].rposition(|&b| b == byte)
+    }
+
+    /// Returns the index of the first occurrence of `needle`, which may overlap `self`.
+    ///
+    /// An empty `needle` is found at index 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let s = SliceExists::from_ref(b"abcabd");
+    /// assert_eq!(s.find_subslice(b"abd"), Some(3));
+    /// assert_eq!(s.find_subslice(&s[1..3]), Some(1));
+    /// assert_eq!(s.find_subslice(b"abcabda"), None);
+    /// ```
+    pub fn find_subslice<S>(&self, needle: &S) -> Option<usize>
+    where
+        S: AsRef<SliceExists<u8>> + ?Sized,
+    {
+        let needle = needle.as_ref();
+        let first = match needle.get(0) {
+            Some(first) => first.get(),
+            None => return Some(0),
+        };
+        let last_start = self.len().checked_sub(needle.len())?;
+        let mut start = 0;
+        while start <= last_start {
+            start += self[start..=last_start].find(first)?;
+            if self[start..].starts_with(needle) {
+                return Some(start);
+            }
+            start += 1;
+        }
+        None
+    }
+
+    /// Returns the end of the `N` bytes starting at `offset`.
+    ///
+    /// If it overflows, this is the error `try_get` gives for an end of `usize::MAX`
+    /// that cannot be made exclusive.
+    #[inline]
+    fn array_end<const N: usize>(&self, offset: usize) -> Result<usize, IndexError> {
+        offset.checked_add(N).ok_or(IndexError::new(
+            IndexErrorKind::EndOverflow,
+            usize::MAX,
+            self.len(),
+        ))
+    }
+
+    /// Reads the `N` bytes starting at `offset`.
+    #[inline]
+    fn read_array<const N: usize>(&self, offset: usize) -> Result<[u8; N], IndexError> {
+        let s = self.try_get(offset..self.array_end::<N>(offset)?)?;
+        // Safety: `s` has length `N`, and `[u8; N]` has an alignment of 1.
+        Ok(unsafe { s.as_ptr().cast::<[u8; N]>().read() })
+    }
+
+    /// Writes `bytes` to the `N` bytes starting at `offset`.
+    #[inline]
+    fn write_array<const N: usize>(
+        &mut self,
+        offset: usize,
+        bytes: [u8; N],
+    ) -> Result<(), IndexError> {
+        let s = self.try_get_mut(offset..self.array_end::<N>(offset)?)?;
+        // Safety: `s` has length `N`, and `[u8; N]` has an alignment of 1.
+        unsafe { s.as_mut_ptr().cast::<[u8; N]>().write(bytes) };
+        Ok(())
+    }
+}
+
+macro_rules! impl_read_write {
+    ($($t:ty: $read_le:ident $read_be:ident $write_le:ident $write_be:ident;)*) => {
+        impl SliceExists<u8> {$(
+            #[doc = concat!("Reads a little-endian `", stringify!($t), "` from the bytes at `offset`.")]
+            ///
+            /// The bytes need not be aligned for the type.
+            ///
+            /// # Errors
+            /// Returns an error, like [`try_get`](SliceExists::try_get), if the bytes
+            /// are out of bounds.
+            #[inline]
+            pub fn $read_le(&self, offset: usize) -> Result<$t, IndexError> {
+                self.read_array(offset).map(<$t>::from_le_bytes)
+            }
+
+            #[doc = concat!("Reads a big-endian `", stringify!($t), "` from the bytes at `offset`.")]
+            ///
+            #[doc = concat!("See [`", stringify!($read_le), "`](SliceExists::", stringify!($read_le), ").")]
+            #[inline]
+            pub fn $read_be(&self, offset: usize) -> Result<$t, IndexError> {
+                self.read_array(offset).map(<$t>::from_be_bytes)
+            }
+
+            #[doc = concat!("Writes a little-endian `", stringify!($t), "` to the bytes at `offset`.")]
+            ///
+            /// The bytes need not be aligned for the type.
+            ///
+            /// # Errors
+            /// Returns an error, like [`try_get_mut`](SliceExists::try_get_mut), if the
+            /// bytes are out of bounds. Nothing is written in that case.
+            #[inline]
+            pub fn $write_le(&mut self, offset: usize, value: $t) -> Result<(), IndexError> {
+                self.write_array(offset, value.to_le_bytes())
+            }
+
+            #[doc = concat!("Writes a big-endian `", stringify!($t), "` to the bytes at `offset`.")]
+            ///
+            #[doc = concat!("See [`", stringify!($write_le), "`](SliceExists::", stringify!($write_le), ").")]
+            #[inline]
+            pub fn $write_be(&mut self, offset: usize, value: $t) -> Result<(), IndexError> {
+                self.write_array(offset, value.to_be_bytes())
+            }
+        )*}
+    };
+}
+
+impl_read_write! {
+    u16: read_u16_le read_u16_be write_u16_le write_u16_be;
+    u32: read_u32_le read_u32_be write_u32_le write_u32_be;
+    u64: read_u64_le read_u64_be write_u64_le write_u64_be;
+    u128: read_u128_le read_u128_be write_u128_le write_u128_be;
+    i16: read_i16_le read_i16_be write_i16_le write_i16_be;
+    i32: read_i32_le read_i32_be write_i32_le write_i32_be;
+    i64: read_i64_le read_i64_be write_i64_le write_i64_be;
+    i128: read_i128_le read_i128_be write_i128_le write_i128_be;
+    f32: read_f32_le read_f32_be write_f32_le write_f32_be;
+    f64: read_f64_le read_f64_be write_f64_le write_f64_be;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slice::IndexErrorKind;
+    use crate::SliceExists;
+
+    #[test]
+    fn find_every_offset() {
+        let mut buf = [0u8; 40];
+        for i in 0..buf.len() {
+            buf[i] = 1;
+            let s = SliceExists::from_ref(&buf);
+            assert_eq!(s.find(1), Some(i));
+            assert_eq!(s.rfind(1), Some(i));
+            assert_eq!(s[..i].find(1), None);
+            assert_eq!(s[i + 1..].rfind(1), None);
+            buf[i] = 0;
+        }
+    }
+
+    #[test]
+    fn find_high_bytes() {
+        let buf = [0x80u8, 0xff, 0x7f, 0x00, 0x81, 0xff, 0x80, 0x01, 0xfe];
+        let s = SliceExists::from_ref(&buf);
+        assert_eq!(s.find(0xff), Some(1));
+        assert_eq!(s.rfind(0xff), Some(5));
+        assert_eq!(s.find(0xfe), Some(8));
+        assert_eq!(s.find(0x02), None);
+    }
+
+    #[test]
+    fn find_subslice() {
+        let s = SliceExists::from_ref(b"aaaaab");
+        assert_eq!(s.find_subslice(b""), Some(0));
+        assert_eq!(s.find_subslice(b"aab"), Some(3));
+        assert_eq!(s.find_subslice(b"b"), Some(5));
+        assert_eq!(s.find_subslice(b"ba"), None);
+        assert_eq!(s[..0].find_subslice(b"a"), None);
+    }
+
+    #[test]
+    fn endian_read_write() {
+        let mut buf = [0u8; 11];
+        let s = SliceExists::from_mut(&mut buf);
+        s.write_u32_be(1, 0x0102_0304).unwrap();
+        s.write_i16_le(5, -2).unwrap();
+        s.write_f32_be(7, 1.5).unwrap();
+        assert_eq!(s.read_u32_be(1), Ok(0x0102_0304));
+        assert_eq!(s.read_u32_le(1), Ok(0x0403_0201));
+        assert_eq!(s.read_i16_le(5), Ok(-2));
+        assert_eq!(s.read_u16_be(5), Ok(0xfeff));
+        assert_eq!(s.read_f32_be(7), Ok(1.5));
+        assert_eq!(buf, [0, 1, 2, 3, 4, 0xfe, 0xff, 0x3f, 0xc0, 0, 0]);
+    }
+
+    #[test]
+    fn endian_out_of_bounds() {
+        let mut buf = [0u8; 6];
+        let s = SliceExists::from_mut(&mut buf);
+        let err = s.read_u32_le(3).unwrap_err();
+        assert_eq!(
+            (err.kind(), err.index(), err.len()),
+            (IndexErrorKind::EndOutOfBounds, 7, 6)
+        );
+        let err = s.write_u64_be(usize::MAX, 1).unwrap_err();
+        assert_eq!(err.kind(), IndexErrorKind::EndOverflow);
+        let err = s.read_u16_be(usize::MAX - 1).unwrap_err();
+        assert_eq!(
+            (err.kind(), err.index(), err.len()),
+            (IndexErrorKind::EndOverflow, usize::MAX, 6)
+        );
+        let err = s.read_u16_le(usize::MAX - 2).unwrap_err();
+        assert_eq!(err.kind(), IndexErrorKind::StartOutOfBounds);
+        assert!(s.write_u16_le(5, 0xffff).is_err());
+        assert_eq!(s.read_u16_le(4), Ok(0));
+        let err = s[6..].read_u128_be(0).unwrap_err();
+        assert_eq!((err.index(), err.len()), (16, 0));
+    }
+}
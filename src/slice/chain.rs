@@ -0,0 +1,752 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scatter-gather lists of existential slices.
+
+use core::iter::FusedIterator;
+use core::ops::{Index, IndexMut, RangeBounds};
+use core::{array, mem, slice};
+
+use super::index::{self, IndexError, IndexErrorKind};
+use super::{len_mismatch_fail, Copied, SliceExists};
+use crate::Exists;
+
+/// Up to `N` mutable existential slices, treated as one logical sequence.
+///
+/// Logical index 0 is the first element of the first segment, and each segment's
+/// elements follow the previous segment's. This is useful for vectored I/O buffers.
+/// The segments are stored inline, so no operation allocates.
+///
+/// # Examples
+/// ```
+/// # use exists_ref::{slice::SliceExistsChain, SliceExists};
+/// let mut header = [0u8; 2];
+/// let mut body = [0u8; 3];
+/// let mut chain = SliceExistsChain::<u8, 4>::from_segments([
+///     SliceExists::from_mut(&mut header),
+///     SliceExists::from_mut(&mut body),
+/// ]);
+/// assert_eq!(chain.len(), 5);
+/// chain.copy_from_slice(SliceExists::from_ref(b"hello"));
+/// chain.range_mut(1..4).iter_mut().for_each(|e| e.set(b'_'));
+/// assert_eq!((&header, &body), (b"h_", b"__o"));
+/// ```
+pub struct SliceExistsChain<'a, T, const N: usize> {
+    segs: [&'a mut SliceExists<T>; N],
+    count: usize,
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn out_of_bounds_fail(index: usize, len: usize) -> ! {
+    panic!(
+        "{}",
+        IndexError::new(IndexErrorKind::OutOfBounds, index, len)
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn capacity_fail(count: usize, capacity: usize) -> ! {
+    panic!(
+        "{} segments do not fit in a chain of capacity {}",
+        count, capacity
+    )
+}
+
+impl<T, const N: usize> Default for SliceExistsChain<'_, T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const N: usize> SliceExistsChain<'a, T, N> {
+    /// Creates an empty chain.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            segs: array::from_fn(|_| Default::default()),
+            count: 0,
+        }
+    }
+
+    /// Creates a chain of the given segments.
+    ///
+    /// # Panics
+    /// Panics if there are more than `N` segments.
+    #[track_caller]
+    pub fn from_segments<I>(segs: I) -> Self
+    where
+        I: IntoIterator<Item = &'a mut SliceExists<T>>,
+    {
+        let mut chain = Self::new();
+        for (count, seg) in segs.into_iter().enumerate() {
+            if chain.push(seg).is_err() {
+                capacity_fail(count + 1, N);
+            }
+        }
+        chain
+    }
+
+    /// Appends a segment to the end of the chain.
+    ///
+    /// Returns the segment back if the chain already has `N` segments.
+    #[inline]
+    pub fn push(&mut self, seg: &'a mut SliceExists<T>) -> Result<(), &'a mut SliceExists<T>> {
+        match self.segs.get_mut(self.count) {
+            Some(slot) => {
+                *slot = seg;
+                self.count += 1;
+                Ok(())
+            }
+            None => Err(seg),
+        }
+    }
+
+    /// Returns the total number of elements in all segments.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments().map(SliceExists::len).sum()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments().all(SliceExists::is_empty)
+    }
+
+    /// Returns the segments of the chain.
+    #[inline]
+    pub fn as_segments(&self) -> &[&'a mut SliceExists<T>] {
+        &self.segs[..self.count]
+    }
+
+    /// Returns the segments of the chain, which may be modified or replaced in place.
+    #[inline]
+    pub fn as_segments_mut(&mut self) -> &mut [&'a mut SliceExists<T>] {
+        &mut self.segs[..self.count]
+    }
+
+    /// Returns an iterator over the segments of the chain.
+    #[inline]
+    pub fn segments(&self) -> Segments<'_, T> {
+        Segments {
+            iter: self.as_segments().iter(),
+        }
+    }
+
+    /// Returns an iterator over the mutable segments of the chain.
+    #[inline]
+    pub fn segments_mut(&mut self) -> SegmentsMut<'_, 'a, T> {
+        SegmentsMut {
+            iter: self.as_segments_mut().iter_mut(),
+        }
+    }
+
+    /// Returns a shorter-lived chain of the same segments.
+    #[inline]
+    pub fn reborrow(&mut self) -> SliceExistsChain<'_, T, N> {
+        SliceExistsChain::from_segments(self.segments_mut())
+    }
+
+    /// Returns the segment index and offset within it of logical index `i`.
+    #[inline]
+    fn locate(&self, mut i: usize) -> Option<(usize, usize)> {
+        for (n, seg) in self.segments().enumerate() {
+            if i < seg.len() {
+                return Some((n, i));
+            }
+            i -= seg.len();
+        }
+        None
+    }
+
+    /// Returns an existential reference to the element at logical index `i`,
+    /// or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&Exists<T>> {
+        let (n, i) = self.locate(i)?;
+        Some(&self.segs[n][i])
+    }
+
+    /// Returns a mutable existential reference to the element at logical index `i`,
+    /// or `None` if it is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut Exists<T>> {
+        let (n, i) = self.locate(i)?;
+        Some(&mut self.segs[n][i])
+    }
+
+    /// Returns the elements in the logical `range`, which may span segments, as one
+    /// piece per segment.
+    ///
+    /// Piece `n` is the part of segment `n` that lies in the range. It is empty if the
+    /// segment does not overlap the range, or if the chain has no segment `n`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::{slice::SliceExistsChain, SliceExists};
+    /// let (mut a, mut b) = ([0, 1, 2], [3, 4]);
+    /// let chain = SliceExistsChain::<_, 3>::from_segments([
+    ///     SliceExists::from_mut(&mut a),
+    ///     SliceExists::from_mut(&mut b),
+    /// ]);
+    /// let [x, y, z] = chain.range(2..4);
+    /// assert_eq!((x.len(), y.len(), z.len()), (1, 1, 0));
+    /// assert_eq!((x[0].get(), y[0].get()), (2, 3));
+    /// ```
+    #[track_caller]
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> [&SliceExists<T>; N] {
+        let range = index::range(range, self.len());
+        let mut pieces = [<&SliceExists<T>>::default(); N];
+        let mut begin = 0;
+        for (piece, seg) in pieces.iter_mut().zip(self.segments()) {
+            let end = begin + seg.len();
+            let lo = range.start.clamp(begin, end) - begin;
+            let hi = range.end.clamp(begin, end) - begin;
+            *piece = &seg[lo..hi];
+            begin = end;
+        }
+        pieces
+    }
+
+    /// Returns a chain of the elements in the logical `range`, which may span segments.
+    ///
+    /// Empty pieces are omitted, so the result has at most as many segments as `self`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    #[track_caller]
+    pub fn range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> SliceExistsChain<'_, T, N> {
+        let range = index::range(range, self.len());
+        let mut chain = SliceExistsChain::new();
+        let mut begin = 0;
+        for seg in self.segments_mut() {
+            let end = begin + seg.len();
+            let lo = range.start.clamp(begin, end) - begin;
+            let hi = range.end.clamp(begin, end) - begin;
+            if lo < hi {
+                // There are at most as many pieces as segments.
+                let _ = chain.push(&mut seg[lo..hi]);
+            }
+            begin = end;
+        }
+        chain
+    }
+
+    /// Returns an iterator over the elements of the chain, in logical order.
+    #[inline]
+    pub fn iter(&self) -> ChainIter<'_, T> {
+        ChainIter {
+            front: Default::default(),
+            back: Default::default(),
+            segs: self.segments(),
+        }
+    }
+
+    /// Returns an iterator over mutable existential references to the elements
+    /// of the chain, in logical order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> ChainIterMut<'_, 'a, T> {
+        ChainIterMut {
+            front: Default::default(),
+            back: Default::default(),
+            segs: self.segments_mut(),
+        }
+    }
+}
+
+impl<T: Copy, const N: usize> SliceExistsChain<'_, T, N> {
+    /// Returns an iterator that reads each element by value, in logical order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::{slice::SliceExistsChain, SliceExists};
+    /// let (mut a, mut b) = ([1, 2], [3]);
+    /// let chain = SliceExistsChain::<_, 2>::from_segments([
+    ///     SliceExists::from_mut(&mut a),
+    ///     SliceExists::from_mut(&mut b),
+    /// ]);
+    /// assert!(chain.values().eq([1, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Copied<ChainIter<'_, T>> {
+        Copied::new(self.iter())
+    }
+
+    /// Copies all elements from `src` into the chain.
+    ///
+    /// This performs one [`SliceExists::copy_from`] per segment. Each is a memmove,
+    /// but if `src` overlaps the chain, later copies read any values written by
+    /// earlier ones.
+    ///
+    /// # Panics
+    /// Panics if `src` and the chain have different lengths.
+    #[track_caller]
+    pub fn copy_from_slice(&mut self, mut src: &SliceExists<T>) {
+        let len = self.len();
+        if src.len() != len {
+            len_mismatch_fail(len, src.len());
+        }
+        for seg in self.segments_mut() {
+            let (head, rest) = src.split_at(seg.len());
+            seg.copy_from(head);
+            src = rest;
+        }
+    }
+
+    /// Copies all elements from the chain into `dst`.
+    ///
+    /// This performs one [`SliceExists::copy_from`] per segment. Each is a memmove,
+    /// but if `dst` overlaps the chain, later copies read any values written by
+    /// earlier ones.
+    ///
+    /// # Panics
+    /// Panics if `dst` and the chain have different lengths.
+    #[track_caller]
+    pub fn copy_to_slice(&self, dst: &mut SliceExists<T>) {
+        let len = self.len();
+        if dst.len() != len {
+            len_mismatch_fail(dst.len(), len);
+        }
+        let mut dst = dst;
+        for seg in self.segments() {
+            let (head, rest) = mem::take(&mut dst).split_at_mut(seg.len());
+            head.copy_from(seg);
+            dst = rest;
+        }
+    }
+
+    /// Fills every segment with `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        self.segments_mut().for_each(|s| s.fill(value));
+    }
+}
+
+impl<T, const N: usize> Index<usize> for SliceExistsChain<'_, T, N> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, i: usize) -> &Exists<T> {
+        match self.get(i) {
+            Some(e) => e,
+            None => out_of_bounds_fail(i, self.len()),
+        }
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for SliceExistsChain<'_, T, N> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, i: usize) -> &mut Exists<T> {
+        let len = self.len();
+        match self.get_mut(i) {
+            Some(e) => e,
+            None => out_of_bounds_fail(i, len),
+        }
+    }
+}
+
+/// An iterator over the segments of a chain.
+///
+/// Created by [`SliceExistsChain::segments`].
+pub struct Segments<'a, T> {
+    iter: slice::Iter<'a, &'a mut SliceExists<T>>,
+}
+
+impl<'a, T> Iterator for Segments<'a, T> {
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|s| &**s)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Segments<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|s| &**s)
+    }
+}
+
+impl<T> Clone for Segments<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for Segments<'_, T> {}
+impl<T> FusedIterator for Segments<'_, T> {}
+
+/// An iterator over the mutable segments of a chain.
+///
+/// Created by [`SliceExistsChain::segments_mut`]. `'a` is the lifetime of the
+/// segments themselves, and `'s` the lifetime of the borrow of the chain.
+pub struct SegmentsMut<'s, 'a, T> {
+    iter: slice::IterMut<'s, &'a mut SliceExists<T>>,
+}
+
+impl<'s, T> Iterator for SegmentsMut<'s, '_, T> {
+    type Item = &'s mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|s| &mut **s)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for SegmentsMut<'_, '_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|s| &mut **s)
+    }
+}
+
+impl<T> ExactSizeIterator for SegmentsMut<'_, '_, T> {}
+impl<T> FusedIterator for SegmentsMut<'_, '_, T> {}
+
+/// An iterator over the elements of a chain, in logical order.
+///
+/// Created by [`SliceExistsChain::iter`].
+pub struct ChainIter<'a, T> {
+    front: &'a SliceExists<T>,
+    back: &'a SliceExists<T>,
+    segs: Segments<'a, T>,
+}
+
+impl<'a, T> Iterator for ChainIter<'a, T> {
+    type Item = &'a Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front.is_empty() {
+            match self.segs.next() {
+                Some(seg) => self.front = seg,
+                None => {
+                    mem::swap(&mut self.front, &mut self.back);
+                    break;
+                }
+            }
+        }
+        let (first, rest) = self.front.split_first()?;
+        self.front = rest;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len()
+            + self.segs.clone().map(SliceExists::len).sum::<usize>()
+            + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for ChainIter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.back.is_empty() {
+            match self.segs.next_back() {
+                Some(seg) => self.back = seg,
+                None => {
+                    mem::swap(&mut self.front, &mut self.back);
+                    break;
+                }
+            }
+        }
+        let (last, rest) = self.back.split_last()?;
+        self.back = rest;
+        Some(last)
+    }
+}
+
+impl<T> Clone for ChainIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            front: self.front,
+            back: self.back,
+            segs: self.segs.clone(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for ChainIter<'_, T> {}
+impl<T> FusedIterator for ChainIter<'_, T> {}
+
+/// An iterator over mutable existential references to the elements of a chain, in
+/// logical order.
+///
+/// Created by [`SliceExistsChain::iter_mut`].
+pub struct ChainIterMut<'s, 'a, T> {
+    front: &'s mut SliceExists<T>,
+    back: &'s mut SliceExists<T>,
+    segs: SegmentsMut<'s, 'a, T>,
+}
+
+impl<'s, T> Iterator for ChainIterMut<'s, '_, T> {
+    type Item = &'s mut Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front.is_empty() {
+            match self.segs.next() {
+                Some(seg) => self.front = seg,
+                None => {
+                    mem::swap(&mut self.front, &mut self.back);
+                    break;
+                }
+            }
+        }
+        let (first, rest) = mem::take(&mut self.front).split_first_mut()?;
+        self.front = rest;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len()
+            + self
+                .segs
+                .iter
+                .as_slice()
+                .iter()
+                .map(|s| s.len())
+                .sum::<usize>()
+            + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for ChainIterMut<'_, '_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.back.is_empty() {
+            match self.segs.next_back() {
+                Some(seg) => self.back = seg,
+                None => {
+                    mem::swap(&mut self.front, &mut self.back);
+                    break;
+                }
+            }
+        }
+        let (last, rest) = mem::take(&mut self.back).split_last_mut()?;
+        self.back = rest;
+        Some(last)
+    }
+}
+
+impl<T> ExactSizeIterator for ChainIterMut<'_, '_, T> {}
+impl<T> FusedIterator for ChainIterMut<'_, '_, T> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn global_indexing() {
+        let (mut a, mut b, mut c) = ([0, 1], [0u32; 0], [2, 3, 4]);
+        let mut chain = SliceExistsChain::<_, 3>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+            SliceExists::from_mut(&mut c),
+        ]);
+        assert_eq!(chain.len(), 5);
+        assert!(chain.values().eq([0, 1, 2, 3, 4]));
+        assert_eq!((chain[1].get(), chain[2].get()), (1, 2));
+        assert!(chain.get(5).is_none());
+        chain[4].set(40);
+        assert_eq!(c, [2, 3, 40]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn index_out_of_bounds() {
+        let (mut a, mut b) = ([0], [1, 2]);
+        let chain = SliceExistsChain::<_, 2>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+        ]);
+        let _ = &chain[3];
+    }
+
+    #[test]
+    fn push() {
+        let (mut a, mut b) = ([0], [1]);
+        let mut chain = SliceExistsChain::<_, 1>::new();
+        assert!(chain.is_empty());
+        assert!(chain.push(SliceExists::from_mut(&mut a)).is_ok());
+        assert!(chain.push(SliceExists::from_mut(&mut b)).is_err());
+        assert_eq!(chain.as_segments().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "2 segments do not fit in a chain of capacity 1")]
+    fn too_many_segments() {
+        let (mut a, mut b) = ([0], [1]);
+        SliceExistsChain::<_, 1>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+        ]);
+    }
+
+    #[test]
+    fn ranges() {
+        let (mut a, mut b, mut c) = ([0, 1, 2], [3, 4], [5, 6, 7]);
+        let mut chain = SliceExistsChain::<_, 3>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+            SliceExists::from_mut(&mut c),
+        ]);
+        let sub = chain.range_mut(2..6);
+        assert_eq!(
+            sub.segments().map(SliceExists::len).collect::<Vec<_>>(),
+            [1, 2, 1]
+        );
+        assert!(sub.values().eq([2, 3, 4, 5]));
+        let mut sub = chain.range_mut(3..5);
+        assert_eq!(sub.as_segments().len(), 1);
+        sub.fill(0);
+        assert_eq!(chain.range_mut(5..5).as_segments().len(), 0);
+        assert!(chain.values().eq([0, 1, 2, 0, 0, 5, 6, 7]));
+    }
+
+    #[test]
+    fn shared_ranges() {
+        let (mut a, mut b, mut c) = ([0, 1, 2], [3, 4], [5, 6, 7]);
+        let chain = SliceExistsChain::<_, 4>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+            SliceExists::from_mut(&mut c),
+        ]);
+        let pieces = chain.range(2..6);
+        assert_eq!(pieces.map(SliceExists::len), [1, 2, 1, 0]);
+        assert!(pieces
+            .iter()
+            .flat_map(|p| p.iter())
+            .map(Exists::get)
+            .eq(2..6));
+        assert_eq!(chain.range(3..5).map(SliceExists::len), [0, 2, 0, 0]);
+        assert_eq!(chain.range(..).map(SliceExists::len), [3, 2, 3, 0]);
+    }
+
+    #[test]
+    fn iterators() {
+        let (mut a, mut b, mut c, mut d) = ([0, 1], [0; 0], [2], [3, 4]);
+        let mut chain = SliceExistsChain::<_, 4>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+            SliceExists::from_mut(&mut c),
+            SliceExists::from_mut(&mut d),
+        ]);
+        assert_eq!(chain.segments().len(), 4);
+        assert_eq!(
+            chain.segments().rev().map(SliceExists::len).sum::<usize>(),
+            5
+        );
+        assert_eq!(chain.iter().len(), 5);
+        assert!(chain.values().rev().eq([4, 3, 2, 1, 0]));
+
+        let mut iter = chain.iter();
+        assert_eq!(iter.next().map(Exists::get), Some(0));
+        assert_eq!(iter.next_back().map(Exists::get), Some(4));
+        assert_eq!(iter.len(), 3);
+        assert!(iter.clone().map(Exists::get).eq([1, 2, 3]));
+        assert_eq!(iter.next_back().map(Exists::get), Some(3));
+        assert_eq!(iter.next_back().map(Exists::get), Some(2));
+        assert_eq!(iter.next_back().map(Exists::get), Some(1));
+        assert!(iter.next().is_none() && iter.next_back().is_none());
+
+        let mut iter = chain.iter_mut();
+        assert_eq!(iter.len(), 5);
+        iter.next_back().unwrap().set(40);
+        iter.next().unwrap().set(10);
+        iter.for_each(|e| e.set(e.get() * 2));
+        for seg in chain.segments_mut().rev() {
+            seg.iter_mut().for_each(|e| e.set(e.get() + 1));
+        }
+        assert!(chain.values().eq([11, 3, 5, 7, 41]));
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 9 out of range for slice of length 8")]
+    fn range_out_of_bounds() {
+        let (mut a, mut b) = ([0; 4], [0; 4]);
+        let mut chain = SliceExistsChain::<_, 2>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+        ]);
+        chain.range_mut(1..9);
+    }
+
+    #[test]
+    fn copy() {
+        let (mut a, mut b) = ([0; 2], [0; 3]);
+        let mut chain = SliceExistsChain::<_, 4>::from_segments([
+            SliceExists::from_mut(&mut a),
+            SliceExists::from_mut(&mut b),
+        ]);
+        chain.copy_from_slice(SliceExists::from_ref(&[1, 2, 3, 4, 5]));
+        let mut out = [0; 4];
+        chain
+            .range_mut(1..)
+            .copy_to_slice(SliceExists::from_mut(&mut out));
+        assert_eq!(out, [2, 3, 4, 5]);
+        assert_eq!((a, b), ([1, 2], [3, 4, 5]));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "source slice length (2) does not match destination slice length (3)"
+    )]
+    fn copy_len_mismatch() {
+        let mut a = [0; 3];
+        SliceExistsChain::<_, 1>::from_segments([SliceExists::from_mut(&mut a)])
+            .copy_from_slice(SliceExists::from_ref(&[0; 2]));
+    }
+
+    #[test]
+    fn aliasing_segments() {
+        // Two segments may overlap, and then writes through one are visible in the other.
+        let mut x = [0, 1, 2];
+        let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+        let mut chain = SliceExistsChain::<_, 2>::from_segments([a, &mut b[1..]]);
+        chain[0].set(10);
+        chain[3].set(20);
+        assert!(chain.values().eq([10, 20, 2, 20, 2]));
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A position-tracking reader and writer over existential byte slices.
+
+use core::{fmt, mem};
+
+use super::index::{IndexError, IndexErrorKind};
+use super::SliceExists;
+
+#[cfg(feature = "std")]
+use std::io;
+
+/// The size of the buffer that [`BufExistsCursor`] copies into.
+#[cfg(feature = "std")]
+const FILL_BUF_LEN: usize = 256;
+
+/// A cursor that reads and writes an existential byte slice, starting at a position
+/// that advances past the bytes read or written.
+///
+/// The position is never past the end of the slice. Operations that would move it
+/// past the end fail with an [`IndexError`] and leave the cursor unchanged, having
+/// read or written nothing.
+///
+/// With the `std` feature, this implements `std::io::Read`, `Write` and `Seek`.
+/// For `BufRead`, wrap it in a `BufExistsCursor`.
+///
+/// # Examples
+/// ```
+/// # use exists_ref::{slice::ExistsCursor, SliceExists};
+/// use core::fmt::Write;
+///
+/// let mut buf = [0u8; 16];
+/// let mut w = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+/// w.write_u16_be(0x0102).unwrap();
+/// write!(w, "hi {}", 5).unwrap();
+/// assert_eq!(w.position(), 6);
+///
+/// let mut r = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+/// assert_eq!(r.read_u16_be(), Ok(0x0102));
+/// assert!(r.take(4).unwrap().values().eq(*b"hi 5"));
+/// assert_eq!(r.remaining(), 10);
+/// ```
+pub struct ExistsCursor<'a> {
+    buf: &'a mut SliceExists<u8>,
+    pos: usize,
+}
+
+impl<'a> ExistsCursor<'a> {
+    /// Creates a cursor at the start of `buf`.
+    #[inline]
+    pub fn new(buf: &'a mut SliceExists<u8>) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Returns the underlying slice.
+    #[inline]
+    pub fn get_ref(&self) -> &SliceExists<u8> {
+        self.buf
+    }
+
+    /// Returns the underlying mutable slice.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut SliceExists<u8> {
+        self.buf
+    }
+
+    /// Returns the underlying mutable slice, consuming the cursor.
+    #[inline]
+    pub fn into_inner(self) -> &'a mut SliceExists<u8> {
+        self.buf
+    }
+
+    /// Returns the position of the cursor.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of bytes after the position.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Moves the cursor to `pos`.
+    ///
+    /// # Errors
+    /// Returns an error if `pos` is past the end of the slice.
+    #[inline]
+    pub fn seek(&mut self, pos: usize) -> Result<(), IndexError> {
+        if pos > self.buf.len() {
+            return Err(IndexError::new(
+                IndexErrorKind::StartOutOfBounds,
+                pos,
+                self.buf.len(),
+            ));
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// Moves the cursor forward by `n` bytes.
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than `n` bytes remaining.
+    #[inline]
+    pub fn skip(&mut self, n: usize) -> Result<(), IndexError> {
+        self.take(n).map(|_| ())
+    }
+
+    /// Returns the next `n` bytes, and moves the cursor past them.
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than `n` bytes remaining.
+    #[inline]
+    pub fn take(&mut self, n: usize) -> Result<&SliceExists<u8>, IndexError> {
+        let s = self.buf.try_get(self.pos..self.pos.saturating_add(n))?;
+        self.pos += n;
+        Ok(s)
+    }
+
+    /// Returns the next `n` bytes as a mutable slice, and moves the cursor past them.
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than `n` bytes remaining.
+    #[inline]
+    pub fn take_mut(&mut self, n: usize) -> Result<&mut SliceExists<u8>, IndexError> {
+        let s = self.buf.try_get_mut(self.pos..self.pos.saturating_add(n))?;
+        self.pos += n;
+        Ok(s)
+    }
+
+    /// Fills `dst` with the next bytes, and moves the cursor past them.
+    ///
+    /// `dst` may overlap the cursor's slice, and is copied to as if by
+    /// [`SliceExists::copy_from`].
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than `dst.len()` bytes remaining.
+    #[inline]
+    pub fn read_exact(&mut self, dst: &mut SliceExists<u8>) -> Result<(), IndexError> {
+        dst.copy_from(self.take(dst.len())?);
+        Ok(())
+    }
+
+    /// Writes all of `src` at the position, and moves the cursor past it.
+    ///
+    /// `src` may overlap the cursor's slice, and is copied from as if by
+    /// [`SliceExists::copy_from`].
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than `src.len()` bytes remaining.
+    #[inline]
+    pub fn write_all(&mut self, src: &SliceExists<u8>) -> Result<(), IndexError> {
+        self.take_mut(src.len())?.copy_from(src);
+        Ok(())
+    }
+
+    /// Reads the next byte.
+    ///
+    /// # Errors
+    /// Returns an error if there are no bytes remaining.
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, IndexError> {
+        Ok(self.take(1)?[0].get())
+    }
+
+    /// Writes a byte.
+    ///
+    /// # Errors
+    /// Returns an error if there are no bytes remaining.
+    #[inline]
+    pub fn write_u8(&mut self, value: u8) -> Result<(), IndexError> {
+        self.take_mut(1)?[0].set(value);
+        Ok(())
+    }
+}
+
+macro_rules! impl_read_write {
+    ($($t:ty: $read_le:ident $read_be:ident $write_le:ident $write_be:ident;)*) => {
+        impl ExistsCursor<'_> {$(
+            #[doc = concat!("Reads a little-endian `", stringify!($t), "`.")]
+            ///
+            /// # Errors
+            /// Returns an error if there are not enough bytes remaining.
+            #[inline]
+            pub fn $read_le(&mut self) -> Result<$t, IndexError> {
+                let value = self.buf.$read_le(self.pos)?;
+                self.pos += mem::size_of::<$t>();
+                Ok(value)
+            }
+
+            #[doc = concat!("Reads a big-endian `", stringify!($t), "`.")]
+            ///
+            /// # Errors
+            /// Returns an error if there are not enough bytes remaining.
+            #[inline]
+            pub fn $read_be(&mut self) -> Result<$t, IndexError> {
+                let value = self.buf.$read_be(self.pos)?;
+                self.pos += mem::size_of::<$t>();
+                Ok(value)
+            }
+
+            #[doc = concat!("Writes a little-endian `", stringify!($t), "`.")]
+            ///
+            /// # Errors
+            /// Returns an error if there are not enough bytes remaining.
+            #[inline]
+            pub fn $write_le(&mut self, value: $t) -> Result<(), IndexError> {
+                self.buf.$write_le(self.pos, value)?;
+                self.pos += mem::size_of::<$t>();
+                Ok(())
+            }
+
+            #[doc = concat!("Writes a big-endian `", stringify!($t), "`.")]
+            ///
+            /// # Errors
+            /// Returns an error if there are not enough bytes remaining.
+            #[inline]
+            pub fn $write_be(&mut self, value: $t) -> Result<(), IndexError> {
+                self.buf.$write_be(self.pos, value)?;
+                self.pos += mem::size_of::<$t>();
+                Ok(())
+            }
+        )*}
+    };
+}
+
+impl_read_write! {
+    u16: read_u16_le read_u16_be write_u16_le write_u16_be;
+    u32: read_u32_le read_u32_be write_u32_le write_u32_be;
+    u64: read_u64_le read_u64_be write_u64_le write_u64_be;
+    u128: read_u128_le read_u128_be write_u128_le write_u128_be;
+    i16: read_i16_le read_i16_be write_i16_le write_i16_be;
+    i32: read_i32_le read_i32_be write_i32_le write_i32_be;
+    i64: read_i64_le read_i64_be write_i64_le write_i64_be;
+    i128: read_i128_le read_i128_be write_i128_le write_i128_be;
+    f32: read_f32_le read_f32_be write_f32_le write_f32_be;
+    f64: read_f64_le read_f64_be write_f64_le write_f64_be;
+}
+
+impl fmt::Write for ExistsCursor<'_> {
+    /// Writes the UTF-8 bytes of `s`, or nothing if they do not fit.
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_all(SliceExists::from_ref(s.as_bytes()))
+            .map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Read for ExistsCursor<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.remaining());
+        self.read_exact(SliceExists::from_mut(&mut buf[..n]))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Write for ExistsCursor<'_> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.remaining());
+        self.write_all(SliceExists::from_ref(&buf[..n]))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Seek for ExistsCursor<'_> {
+    /// Seeks to a position, which must not be past the end of the slice.
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            io::SeekFrom::Start(n) => (0, i64::try_from(n).ok()),
+            io::SeekFrom::End(n) => (self.buf.len(), Some(n)),
+            io::SeekFrom::Current(n) => (self.pos, Some(n)),
+        };
+        let pos = offset
+            .and_then(|n| isize::try_from(n).ok())
+            .and_then(|n| base.checked_add_signed(n))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?;
+        ExistsCursor::seek(self, pos)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Ok(pos as u64)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.pos as u64)
+    }
+}
+
+/// An [`ExistsCursor`] with a small buffer, for `std::io::BufRead`.
+///
+/// Since the bytes may be written through other existential references, they cannot
+/// be lent out directly. [`fill_buf`](io::BufRead::fill_buf) instead copies up to
+/// 256 of the remaining bytes into the buffer. It copies again on every call, so it
+/// observes writes through other references. Reads and seeks go straight to the
+/// cursor.
+///
+/// # Examples
+/// ```
+/// # use exists_ref::{slice::{BufExistsCursor, ExistsCursor}, SliceExists};
+/// use std::io::BufRead;
+///
+/// let mut buf = *b"one\ntwo\n";
+/// let cursor = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+/// let lines: Vec<_> = BufExistsCursor::new(cursor).lines().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, ["one", "two"]);
+/// ```
+#[cfg(feature = "std")]
+pub struct BufExistsCursor<'a> {
+    inner: ExistsCursor<'a>,
+    fill_buf: [u8; FILL_BUF_LEN],
+}
+
+#[cfg(feature = "std")]
+impl<'a> BufExistsCursor<'a> {
+    /// Wraps `inner`, keeping its position.
+    #[inline]
+    pub fn new(inner: ExistsCursor<'a>) -> Self {
+        Self {
+            inner,
+            fill_buf: [0; FILL_BUF_LEN],
+        }
+    }
+
+    /// Returns the underlying cursor.
+    #[inline]
+    pub fn get_ref(&self) -> &ExistsCursor<'a> {
+        &self.inner
+    }
+
+    /// Returns the underlying cursor mutably.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut ExistsCursor<'a> {
+        &mut self.inner
+    }
+
+    /// Returns the underlying cursor, consuming the wrapper.
+    #[inline]
+    pub fn into_inner(self) -> ExistsCursor<'a> {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Read for BufExistsCursor<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::Seek for BufExistsCursor<'_> {
+    #[inline]
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        io::Seek::seek(&mut self.inner, pos)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.inner.stream_position()
+    }
+}
+
+#[cfg(feature = "std")]
+impl io::BufRead for BufExistsCursor<'_> {
+    /// Copies up to 256 of the remaining bytes into the buffer, and returns it.
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let c = &self.inner;
+        let n = FILL_BUF_LEN.min(c.remaining());
+        SliceExists::from_mut(&mut self.fill_buf[..n]).copy_from(&c.buf[c.pos..][..n]);
+        Ok(&self.fill_buf[..n])
+    }
+
+    /// Moves the cursor forward by `amt` bytes, stopping at the end of the slice.
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.inner.pos += amt.min(self.inner.remaining());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_round_trip() {
+        let mut buf = [0u8; 19];
+        let mut c = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+        c.write_u8(7).unwrap();
+        c.write_u32_be(0xdead_beef).unwrap();
+        c.write_i16_le(-3).unwrap();
+        c.write_f64_be(2.5).unwrap();
+        c.skip(2).unwrap();
+        c.write_u16_le(0x1234).unwrap();
+        assert_eq!(c.remaining(), 0);
+        assert!(c.write_u8(0).is_err());
+
+        c.seek(0).unwrap();
+        assert_eq!(c.read_u8(), Ok(7));
+        assert_eq!(c.read_u32_be(), Ok(0xdead_beef));
+        assert_eq!(c.read_i16_le(), Ok(-3));
+        assert_eq!(c.read_f64_be(), Ok(2.5));
+        assert_eq!(c.take(2).unwrap().len(), 2);
+        assert_eq!(c.read_u16_be(), Ok(0x3412));
+        assert_eq!(buf[..5], [7, 0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn errors_leave_cursor_unchanged() {
+        let mut buf = [1u8, 2, 3];
+        let mut c = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+        c.skip(1).unwrap();
+        let err = c.read_u32_le().unwrap_err();
+        assert_eq!(
+            (err.kind(), err.index(), err.len()),
+            (IndexErrorKind::EndOutOfBounds, 5, 3)
+        );
+        let err = c.seek(4).unwrap_err();
+        assert_eq!(err.kind(), IndexErrorKind::StartOutOfBounds);
+        assert!(c.skip(usize::MAX).is_err());
+        let mut dst = [0u8; 3];
+        assert!(c.read_exact(SliceExists::from_mut(&mut dst)).is_err());
+        assert_eq!(c.position(), 1);
+        assert!(c.write_all(SliceExists::from_ref(&[9; 3])).is_err());
+        assert_eq!(buf, [1, 2, 3]);
+    }
+
+    #[test]
+    fn overlapping_copies() {
+        let mut buf = [1u8, 2, 3, 4, 5, 6];
+        let [a, b] = SliceExists::from_mut(&mut buf).copy_mut();
+        let mut c = ExistsCursor::new(a);
+        c.seek(2).unwrap();
+        c.write_all(&b[..4]).unwrap();
+        c.seek(0).unwrap();
+        c.read_exact(&mut b[1..3]).unwrap();
+        assert_eq!(buf, [1, 1, 2, 2, 3, 4]);
+    }
+
+    #[test]
+    fn fmt_write() {
+        use core::fmt::Write;
+
+        let mut buf = [0u8; 8];
+        let mut c = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+        write!(c, "{}-{}", 12, 34).unwrap();
+        assert!(write!(c, "long").is_err());
+        assert_eq!(c.position(), 5);
+        assert_eq!(&buf[..5], b"12-34");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io() {
+        extern crate std;
+        use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+        let mut buf = [0u8; 300];
+        let mut c = ExistsCursor::new(SliceExists::from_mut(&mut buf));
+        assert_eq!(c.write(&[b'a'; 299]).unwrap(), 299);
+        assert_eq!(c.write(b"bc").unwrap(), 1);
+        assert_eq!(c.write(b"d").unwrap(), 0);
+        assert_eq!(Seek::seek(&mut c, SeekFrom::End(-3)).unwrap(), 297);
+        let mut out = [0u8; 4];
+        assert_eq!(c.read(&mut out).unwrap(), 3);
+        assert_eq!(out, *b"aab\0");
+        assert!(Seek::seek(&mut c, SeekFrom::Current(1)).is_err());
+        assert!(Seek::seek(&mut c, SeekFrom::Current(-301)).is_err());
+        assert_eq!(c.stream_position().unwrap(), 300);
+
+        let mut c = BufExistsCursor::new(c);
+        c.rewind().unwrap();
+        assert_eq!(c.fill_buf().unwrap().len(), 256);
+        c.consume(290);
+        let mut line = std::string::String::new();
+        c.read_line(&mut line).unwrap();
+        assert_eq!(line, "aaaaaaaaab");
+        assert_eq!(c.fill_buf().unwrap(), b"");
+        assert_eq!(c.into_inner().position(), 300);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn buf_read_sees_aliased_writes() {
+        extern crate std;
+        use std::io::{BufRead, Read, Seek, SeekFrom};
+
+        let mut buf = *b"abcdef";
+        let [a, b] = SliceExists::from_mut(&mut buf).copy_mut();
+        let mut cursor = ExistsCursor::new(a);
+        cursor.skip(1).unwrap();
+        let mut c = BufExistsCursor::new(cursor);
+        assert_eq!(c.fill_buf().unwrap(), b"bcdef");
+
+        // The buffer is refilled on every call, so it sees writes through `b`.
+        b[2].set(b'X');
+        assert_eq!(c.fill_buf().unwrap(), b"bXdef");
+        c.consume(2);
+        let mut out = [0u8; 2];
+        c.read_exact(&mut out).unwrap();
+        assert_eq!(&out, b"de");
+
+        assert_eq!(c.seek(SeekFrom::Current(-4)).unwrap(), 1);
+        c.get_mut().write_u8(b'Y').unwrap();
+        assert_eq!(c.get_ref().position(), 2);
+        assert_eq!(c.fill_buf().unwrap(), b"Xdef");
+        assert_eq!(c.into_inner().position(), 2);
+        assert_eq!(&buf, b"aYXdef");
+    }
+}
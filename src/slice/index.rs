@@ -14,7 +14,9 @@
 
 use crate::slice::SliceExists;
 use crate::Exists;
-use core::{ops, ptr};
+use core::fmt;
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
+use core::{hint, ops, ptr};
 
 mod sealed {
     use core::ops;
@@ -29,9 +31,12 @@ mod sealed {
     impl Sealed for ops::RangeInclusive<usize> {}
     impl Sealed for ops::RangeToInclusive<usize> {}
     impl Sealed for (ops::Bound<usize>, ops::Bound<usize>) {}
+    impl<I: super::CustomIndex> Sealed for I {}
 }
 
 /// A valid index on an existential slice reference.
+///
+/// This trait is sealed. To index with your own types, implement [`CustomIndex`].
 pub trait SliceExistsIndex<T: ?Sized>: sealed::Sealed + Sized {
     /// The output type returned by methods.
     type Output: ?Sized;
@@ -45,6 +50,7 @@ pub trait SliceExistsIndex<T: ?Sized>: sealed::Sealed + Sized {
     /// Returns a shared reference to the output at this location, without
     /// performing any bounds checking.
     ///
+    /// # Safety
     /// Calling this method with an out-of-bounds index or invalid `slice` is
     /// undefined behavior even if the resulting reference is not used.
     unsafe fn get_unchecked(self, slice: &T) -> &Self::Output;
@@ -52,21 +58,257 @@ pub trait SliceExistsIndex<T: ?Sized>: sealed::Sealed + Sized {
     /// Returns a mutable reference to the output at this location, without
     /// performing any bounds checking.
     ///
+    /// # Safety
     /// Calling this method with an out-of-bounds index or invalid `slice` is
     /// undefined behavior even if the resulting reference is not used.
     unsafe fn get_unchecked_mut(self, slice: &mut T) -> &mut Self::Output;
 
+    /// Returns a shared reference to the output at this location,
+    /// or an error describing why it is out of bounds.
+    fn try_index(self, slice: &T) -> Result<&Self::Output, IndexError>;
+
+    /// Returns a mutable reference to the output at this location,
+    /// or an error describing why it is out of bounds.
+    fn try_index_mut(self, slice: &mut T) -> Result<&mut Self::Output, IndexError>;
+
     /// Returns a shared reference to the output at this location,
     /// panicking if out of bounds.
+    #[inline]
     #[track_caller]
-    fn index(self, slice: &T) -> &Self::Output;
+    fn index(self, slice: &T) -> &Self::Output {
+        match self.try_index(slice) {
+            Ok(output) => output,
+            Err(e) => index_fail(e),
+        }
+    }
 
     /// Returns a mutable reference to the output at this location,
     /// panicking if out of bounds.
+    #[inline]
     #[track_caller]
-    fn index_mut(self, slice: &mut T) -> &mut Self::Output;
+    fn index_mut(self, slice: &mut T) -> &mut Self::Output {
+        match self.try_index_mut(slice) {
+            Ok(output) => output,
+            Err(e) => index_fail(e),
+        }
+    }
+}
+
+/// An index type defined outside of this crate.
+///
+/// [`SliceExistsIndex`] is sealed, but any type implementing this trait also implements it,
+/// so it can be used with [`SliceExists::get`], `[]` indexing, and the other indexing methods.
+/// This allows typed IDs, or index types that return a custom view of the slice.
+///
+/// An implementation provides a bounds check and unchecked accessors. The checked methods of
+/// [`SliceExistsIndex`] call [`check`](CustomIndex::check) and then the unchecked accessor.
+///
+/// # Safety
+/// Safe code can reach [`get_unchecked`](CustomIndex::get_unchecked) and
+/// [`get_unchecked_mut`](CustomIndex::get_unchecked_mut) through the checked methods, so an
+/// implementation must uphold the following:
+/// - If `check` returns `Ok(())` for an index and a slice, calling either unchecked accessor
+///   with the same index and slice must be sound.
+/// - The references returned by the accessors may only give access to memory inside the
+///   `slice` they were passed: every `Exists<U>` or `SliceExists<U>` reachable through the
+///   output must point to properly aligned, initialized `U`s within the slice's memory.
+///   For example, `Exists<T>` for one element, or `SliceExists<T>` for a subrange.
+/// - `get_unchecked` must only give read access, and `get_unchecked_mut` may give read and
+///   write access. Neither may produce a `&T` or `&mut T` to the elements.
+///
+/// # Examples
+/// ```
+/// use exists_ref::{Exists, SliceExists};
+/// use exists_ref::slice::{CustomIndex, IndexError};
+///
+/// #[derive(Clone, Copy)]
+/// struct NodeId(u32);
+///
+/// // Safety: `check` only succeeds for an in-bounds position,
+/// // and the accessors return the element at that position.
+/// unsafe impl CustomIndex for NodeId {
+///     type Output<T> = Exists<T>;
+///
+///     fn check<T>(&self, slice: &SliceExists<T>) -> Result<(), IndexError> {
+///         slice.try_get(self.0 as usize).map(|_| ())
+///     }
+///
+///     unsafe fn get_unchecked<T>(self, slice: &SliceExists<T>) -> &Exists<T> {
+///         slice.get_unchecked(self.0 as usize)
+///     }
+///
+///     unsafe fn get_unchecked_mut<T>(self, slice: &mut SliceExists<T>) -> &mut Exists<T> {
+///         slice.get_unchecked_mut(self.0 as usize)
+///     }
+/// }
+///
+/// let mut weights = [1.0, 2.0, 3.0];
+/// let s = SliceExists::from_mut(&mut weights);
+/// s[NodeId(1)].set(5.0);
+/// assert_eq!(s[NodeId(1)].get(), 5.0);
+/// assert!(s.get(NodeId(3)).is_none());
+/// ```
+pub unsafe trait CustomIndex: Sized {
+    /// The output type when indexing a `SliceExists<T>`.
+    type Output<T>: ?Sized;
+
+    /// Returns `Ok(())` if this index is in bounds for `slice`, or why it is not.
+    fn check<T>(&self, slice: &SliceExists<T>) -> Result<(), IndexError>;
+
+    /// Returns a shared reference to the output at this location, without
+    /// performing any bounds checking.
+    ///
+    /// # Safety
+    /// [`check`](CustomIndex::check) must return `Ok(())` for this index and `slice`.
+    unsafe fn get_unchecked<T>(self, slice: &SliceExists<T>) -> &Self::Output<T>;
+
+    /// Returns a mutable reference to the output at this location, without
+    /// performing any bounds checking.
+    ///
+    /// # Safety
+    /// [`check`](CustomIndex::check) must return `Ok(())` for this index and `slice`.
+    unsafe fn get_unchecked_mut<T>(self, slice: &mut SliceExists<T>) -> &mut Self::Output<T>;
+}
+
+impl<T, I: CustomIndex> SliceExistsIndex<SliceExists<T>> for I {
+    type Output = I::Output<T>;
+
+    #[inline]
+    fn get(self, slice: &SliceExists<T>) -> Option<&Self::Output> {
+        self.try_index(slice).ok()
+    }
+
+    #[inline]
+    fn get_mut(self, slice: &mut SliceExists<T>) -> Option<&mut Self::Output> {
+        self.try_index_mut(slice).ok()
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(self, slice: &SliceExists<T>) -> &Self::Output {
+        CustomIndex::get_unchecked(self, slice)
+    }
+
+    #[inline]
+    unsafe fn get_unchecked_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
+        CustomIndex::get_unchecked_mut(self, slice)
+    }
+
+    #[inline]
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        self.check(slice)?;
+        // Safety: `check` succeeded, which the implementation guarantees makes this sound.
+        Ok(unsafe { CustomIndex::get_unchecked(self, slice) })
+    }
+
+    #[inline]
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        self.check(slice)?;
+        // Safety: `check` succeeded, which the implementation guarantees makes this sound.
+        Ok(unsafe { CustomIndex::get_unchecked_mut(self, slice) })
+    }
+}
+
+/// The reason an index into an existential slice failed.
+///
+/// Returned by [`SliceExistsIndex::try_index`] and [`SliceExists::try_get`], along with the
+/// offending index and the length of the slice.
+/// Its [`Display`](fmt::Display) output is the same message that indexing panics with.
+///
+/// ```
+/// # use exists_ref::SliceExists;
+/// # use exists_ref::slice::IndexErrorKind;
+/// let s = SliceExists::from_ref(&[1, 2, 3]);
+/// let err = s.try_get(1..5).err().unwrap();
+/// assert_eq!(err.kind(), IndexErrorKind::EndOutOfBounds);
+/// assert_eq!((err.index(), err.len()), (5, 3));
+/// assert_eq!(err.to_string(), "range end index 5 out of range for slice of length 3");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IndexError {
+    kind: IndexErrorKind,
+    index: usize,
+    len: usize,
+}
+
+/// The kind of an [`IndexError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IndexErrorKind {
+    /// A position is not less than the length of the slice.
+    OutOfBounds,
+    /// The start of a range is greater than the length of the slice.
+    StartOutOfBounds,
+    /// The end of a range is greater than the length of the slice.
+    EndOutOfBounds,
+    /// The start of a range is greater than its exclusive `end`.
+    StartAfterEnd {
+        /// The exclusive end of the range.
+        end: usize,
+    },
+    /// An excluded start of `usize::MAX` cannot be made inclusive.
+    StartOverflow,
+    /// An included end of `usize::MAX` cannot be made exclusive.
+    EndOverflow,
+}
+
+impl IndexError {
+    /// Creates an error of the given kind, for the offending `index` into a slice of length `len`.
+    ///
+    /// This is useful for implementations of [`CustomIndex::check`].
+    #[inline]
+    pub const fn new(kind: IndexErrorKind, index: usize, len: usize) -> Self {
+        Self { kind, index, len }
+    }
+
+    /// Returns the kind of error.
+    #[inline]
+    pub const fn kind(&self) -> IndexErrorKind {
+        self.kind
+    }
+
+    /// Returns the offending index. For ranges, this is the endpoint that was out of bounds,
+    /// or the start if it was after the end.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the length of the slice that was indexed.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (index, len) = (self.index, self.len);
+        match self.kind {
+            IndexErrorKind::OutOfBounds => write!(
+                f,
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+            IndexErrorKind::StartOutOfBounds | IndexErrorKind::StartOverflow => write!(
+                f,
+                "range start index {} out of range for slice of length {}",
+                index, len
+            ),
+            IndexErrorKind::EndOutOfBounds | IndexErrorKind::EndOverflow => write!(
+                f,
+                "range end index {} out of range for slice of length {}",
+                index, len
+            ),
+            IndexErrorKind::StartAfterEnd { end } => {
+                write!(f, "slice index starts at {} but ends at {}", index, end)
+            }
+        }
+    }
 }
 
+impl core::error::Error for IndexError {}
+
 impl<T> SliceExistsIndex<SliceExists<T>> for usize {
     type Output = Exists<T>;
 
@@ -91,16 +333,17 @@ impl<T> SliceExistsIndex<SliceExists<T>> for usize {
     }
 
     #[inline]
-    fn index(self, slice: &SliceExists<T>) -> &Self::Output {
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        let len = slice.len();
         self.get(slice)
-            .unwrap_or_else(|| slice_index_past_end(self, slice.len()))
+            .ok_or(IndexError::new(IndexErrorKind::OutOfBounds, self, len))
     }
 
     #[inline]
-    fn index_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
         let len = slice.len();
         self.get_mut(slice)
-            .unwrap_or_else(|| slice_index_past_end(self, len))
+            .ok_or(IndexError::new(IndexErrorKind::OutOfBounds, self, len))
     }
 }
 
@@ -109,13 +352,13 @@ impl<T> SliceExistsIndex<SliceExists<T>> for ops::Range<usize> {
 
     #[inline]
     fn get(self, slice: &SliceExists<T>) -> Option<&Self::Output> {
-        (self.start > self.end || self.end > slice.len())
+        (self.start <= self.end && self.end <= slice.len())
             .then(|| unsafe { self.get_unchecked(slice) })
     }
 
     #[inline]
     fn get_mut(self, slice: &mut SliceExists<T>) -> Option<&mut Self::Output> {
-        (self.start > self.end || self.end > slice.len())
+        (self.start <= self.end && self.end <= slice.len())
             .then(|| unsafe { self.get_unchecked_mut(slice) })
     }
 
@@ -136,23 +379,15 @@ impl<T> SliceExistsIndex<SliceExists<T>> for ops::Range<usize> {
     }
 
     #[inline]
-    fn index(self, slice: &SliceExists<T>) -> &Self::Output {
-        if self.start > self.end {
-            slice_index_order_fail(self.start, self.end)
-        } else if self.end > slice.len() {
-            slice_end_index_len_fail(self.end, slice.len())
-        }
-        unsafe { self.get_unchecked(slice) }
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        let range = try_into_range(slice.len(), (Included(self.start), Excluded(self.end)))?;
+        Ok(unsafe { range.get_unchecked(slice) })
     }
 
     #[inline]
-    fn index_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
-        if self.start > self.end {
-            slice_index_order_fail(self.start, self.end)
-        } else if self.end > slice.len() {
-            slice_end_index_len_fail(self.end, slice.len())
-        }
-        unsafe { self.get_unchecked_mut(slice) }
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        let range = try_into_range(slice.len(), (Included(self.start), Excluded(self.end)))?;
+        Ok(unsafe { range.get_unchecked_mut(slice) })
     }
 }
 
@@ -180,13 +415,13 @@ impl<T> SliceExistsIndex<SliceExists<T>> for ops::RangeTo<usize> {
     }
 
     #[inline]
-    fn index(self, slice: &SliceExists<T>) -> &Self::Output {
-        (0..self.end).index(slice)
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        (0..self.end).try_index(slice)
     }
 
     #[inline]
-    fn index_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
-        (0..self.end).index_mut(slice)
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        (0..self.end).try_index_mut(slice)
     }
 }
 
@@ -214,19 +449,13 @@ impl<T> SliceExistsIndex<SliceExists<T>> for ops::RangeFrom<usize> {
     }
 
     #[inline]
-    fn index(self, slice: &SliceExists<T>) -> &Self::Output {
-        if self.start > slice.len() {
-            slice_start_index_len_fail(self.start, slice.len());
-        }
-        unsafe { self.get_unchecked(slice) }
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        (self.start..slice.len()).try_index(slice)
     }
 
     #[inline]
-    fn index_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
-        if self.start > slice.len() {
-            slice_start_index_len_fail(self.start, slice.len());
-        }
-        unsafe { self.get_unchecked_mut(slice) }
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        (self.start..slice.len()).try_index_mut(slice)
     }
 }
 
@@ -254,41 +483,56 @@ impl<T> SliceExistsIndex<SliceExists<T>> for ops::RangeFull {
     }
 
     #[inline]
-    fn index(self, slice: &SliceExists<T>) -> &Self::Output {
-        slice
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        Ok(slice)
     }
 
     #[inline]
-    fn index_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
-        slice
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        Ok(slice)
     }
 }
 
 impl<T> SliceExistsIndex<SliceExists<T>> for ops::RangeInclusive<usize> {
     type Output = SliceExists<T>;
 
-    fn get(self, _slice: &SliceExists<T>) -> Option<&Self::Output> {
-        todo!()
+    #[inline]
+    fn get(self, slice: &SliceExists<T>) -> Option<&Self::Output> {
+        inclusive_bounds(&self).get(slice)
     }
 
-    fn get_mut(self, _slice: &mut SliceExists<T>) -> Option<&mut Self::Output> {
-        todo!()
+    #[inline]
+    fn get_mut(self, slice: &mut SliceExists<T>) -> Option<&mut Self::Output> {
+        inclusive_bounds(&self).get_mut(slice)
     }
 
-    unsafe fn get_unchecked(self, _slice: &SliceExists<T>) -> &Self::Output {
-        todo!()
+    #[inline]
+    unsafe fn get_unchecked(self, slice: &SliceExists<T>) -> &Self::Output {
+        inclusive_bounds(&self).get_unchecked(slice)
     }
 
-    unsafe fn get_unchecked_mut(self, _slice: &mut SliceExists<T>) -> &mut Self::Output {
-        todo!()
+    #[inline]
+    unsafe fn get_unchecked_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
+        inclusive_bounds(&self).get_unchecked_mut(slice)
     }
 
-    fn index(self, _slice: &SliceExists<T>) -> &Self::Output {
-        todo!()
+    #[inline]
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        // Like core, an exhausted range is checked as if it were not exhausted.
+        try_into_range(
+            slice.len(),
+            (Included(*self.start()), Included(*self.end())),
+        )?;
+        Ok(unsafe { inclusive_bounds(&self).get_unchecked(slice) })
     }
 
-    fn index_mut(self, _slice: &mut SliceExists<T>) -> &mut Self::Output {
-        todo!()
+    #[inline]
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        try_into_range(
+            slice.len(),
+            (Included(*self.start()), Included(*self.end())),
+        )?;
+        Ok(unsafe { inclusive_bounds(&self).get_unchecked_mut(slice) })
     }
 }
 
@@ -316,46 +560,547 @@ impl<T> SliceExistsIndex<SliceExists<T>> for ops::RangeToInclusive<usize> {
     }
 
     #[inline]
-    fn index(self, slice: &SliceExists<T>) -> &Self::Output {
-        (0..=self.end).index(slice)
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        (0..=self.end).try_index(slice)
     }
 
     #[inline]
-    fn index_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
-        (0..=self.end).index_mut(slice)
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        (0..=self.end).try_index_mut(slice)
     }
 }
 
-#[inline(never)]
-#[cold]
-#[track_caller]
-fn slice_index_past_end(index: usize, len: usize) -> ! {
-    panic!("index {} out of range for slice of length {}", index, len)
+impl<T> SliceExistsIndex<SliceExists<T>> for (Bound<usize>, Bound<usize>) {
+    type Output = SliceExists<T>;
+
+    #[inline]
+    fn get(self, slice: &SliceExists<T>) -> Option<&Self::Output> {
+        into_range(slice.len(), self)?.get(slice)
+    }
+
+    #[inline]
+    fn get_mut(self, slice: &mut SliceExists<T>) -> Option<&mut Self::Output> {
+        into_range(slice.len(), self)?.get_mut(slice)
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(self, slice: &SliceExists<T>) -> &Self::Output {
+        match into_range(slice.len(), self) {
+            Some(range) => range.get_unchecked(slice),
+            // Safety: an endpoint that overflows is out of bounds.
+            None => hint::unreachable_unchecked(),
+        }
+    }
+
+    #[inline]
+    unsafe fn get_unchecked_mut(self, slice: &mut SliceExists<T>) -> &mut Self::Output {
+        match into_range(slice.len(), self) {
+            Some(range) => range.get_unchecked_mut(slice),
+            // Safety: an endpoint that overflows is out of bounds.
+            None => hint::unreachable_unchecked(),
+        }
+    }
+
+    #[inline]
+    fn try_index(self, slice: &SliceExists<T>) -> Result<&Self::Output, IndexError> {
+        let range = try_into_slice_range(slice.len(), self)?;
+        Ok(unsafe { range.get_unchecked(slice) })
+    }
+
+    #[inline]
+    fn try_index_mut(self, slice: &mut SliceExists<T>) -> Result<&mut Self::Output, IndexError> {
+        let range = try_into_slice_range(slice.len(), self)?;
+        Ok(unsafe { range.get_unchecked_mut(slice) })
+    }
 }
 
-#[inline(never)]
-#[cold]
-#[track_caller]
-fn slice_index_order_fail(index: usize, end: usize) -> ! {
-    panic!("slice index starts at {} but ends at {}", index, end)
+/// Converts a `RangeInclusive` to equivalent bounds.
+///
+/// An exhausted range like `1..=1` after iteration is treated as the empty range `2..2`,
+/// matching core.
+#[inline]
+fn inclusive_bounds(range: &ops::RangeInclusive<usize>) -> (Bound<usize>, Bound<usize>) {
+    let (start, end) = (*range.start(), *range.end());
+    if range.is_empty() && start <= end {
+        (Excluded(end), Included(end))
+    } else {
+        (Included(start), Included(end))
+    }
 }
 
-#[inline(never)]
-#[cold]
+/// Converts bounds into a half-open range, or `None` if an endpoint overflows `usize`.
+///
+/// The result is not checked against `len`.
+#[inline]
+fn into_range(len: usize, (start, end): (Bound<usize>, Bound<usize>)) -> Option<ops::Range<usize>> {
+    let start = match start {
+        Included(start) => start,
+        Excluded(start) => start.checked_add(1)?,
+        Unbounded => 0,
+    };
+    let end = match end {
+        Included(end) => end.checked_add(1)?,
+        Excluded(end) => end,
+        Unbounded => len,
+    };
+    Some(start..end)
+}
+
+/// Converts the bounds of a core range type into a half-open range that is in bounds for
+/// `len`, or describes why not.
+///
+/// Like indexing with a `Range` or `RangeInclusive` in core, the start is checked against
+/// `len` first, then the end, then their order. An inclusive end is reported as written,
+/// while an order failure reports the exclusive end.
+#[inline]
+fn try_into_range(
+    len: usize,
+    (start, end): (Bound<usize>, Bound<usize>),
+) -> Result<ops::Range<usize>, IndexError> {
+    let start = match start {
+        Included(start) => start,
+        Excluded(start) => start.checked_add(1).ok_or(IndexError::new(
+            IndexErrorKind::StartOverflow,
+            start,
+            len,
+        ))?,
+        Unbounded => 0,
+    };
+    if start > len {
+        return Err(IndexError::new(
+            IndexErrorKind::StartOutOfBounds,
+            start,
+            len,
+        ));
+    }
+    let end = match end {
+        Included(end) => end
+            .checked_add(1)
+            .ok_or(IndexError::new(IndexErrorKind::EndOverflow, end, len))
+            .and_then(|excl| {
+                if excl > len {
+                    Err(IndexError::new(IndexErrorKind::EndOutOfBounds, end, len))
+                } else {
+                    Ok(excl)
+                }
+            })?,
+        Excluded(end) if end > len => {
+            return Err(IndexError::new(IndexErrorKind::EndOutOfBounds, end, len))
+        }
+        Excluded(end) => end,
+        Unbounded => len,
+    };
+    if start > end {
+        return Err(IndexError::new(
+            IndexErrorKind::StartAfterEnd { end },
+            start,
+            len,
+        ));
+    }
+    Ok(start..end)
+}
+
+/// Converts a pair of bounds into a half-open range that is in bounds for `len`, or
+/// describes why not.
+///
+/// Like indexing with a bound pair in core, and unlike core's range types, the end is
+/// checked against `len` first, then the start, then their order. Both endpoints are
+/// reported as written, except that an order failure reports the exclusive end. An
+/// excluded start equal to the exclusive end is reported as that end being out of range,
+/// as core does. The overflow kinds are used for the `usize::MAX` endpoints that core
+/// reports as out of range, and display the same way.
+#[inline]
+fn try_into_slice_range(
+    len: usize,
+    (start, end): (Bound<usize>, Bound<usize>),
+) -> Result<ops::Range<usize>, IndexError> {
+    let end = match end {
+        Included(usize::MAX) => {
+            return Err(IndexError::new(
+                IndexErrorKind::EndOverflow,
+                usize::MAX,
+                len,
+            ))
+        }
+        Included(end) if end >= len => {
+            return Err(IndexError::new(IndexErrorKind::EndOutOfBounds, end, len))
+        }
+        Included(end) => end + 1,
+        Excluded(end) if end > len => {
+            return Err(IndexError::new(IndexErrorKind::EndOutOfBounds, end, len))
+        }
+        Excluded(end) => end,
+        Unbounded => len,
+    };
+    let start = match start {
+        Excluded(usize::MAX) if usize::MAX > len => {
+            return Err(IndexError::new(
+                IndexErrorKind::StartOverflow,
+                usize::MAX,
+                len,
+            ))
+        }
+        Included(start) | Excluded(start) if start > len => {
+            return Err(IndexError::new(
+                IndexErrorKind::StartOutOfBounds,
+                start,
+                len,
+            ))
+        }
+        Included(start) | Excluded(start) if start > end => {
+            return Err(IndexError::new(
+                IndexErrorKind::StartAfterEnd { end },
+                start,
+                len,
+            ))
+        }
+        Excluded(start) if start == end => {
+            return Err(IndexError::new(IndexErrorKind::EndOutOfBounds, end, len))
+        }
+        Included(start) => start,
+        // `start < end <= len`, so this cannot overflow.
+        Excluded(start) => start + 1,
+        Unbounded => 0,
+    };
+    Ok(start..end)
+}
+
+/// Converts any `RangeBounds<usize>` into a `Range<usize>` checked against `len`,
+/// panicking with the same messages as indexing if it is invalid.
 #[track_caller]
-fn slice_start_index_len_fail(index: usize, len: usize) -> ! {
-    panic!(
-        "range start index {} out of range for slice of length {}",
-        index, len
-    )
+pub(crate) fn range<R>(range: R, len: usize) -> ops::Range<usize>
+where
+    R: ops::RangeBounds<usize>,
+{
+    let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+    match try_into_range(len, bounds) {
+        Ok(range) => range,
+        Err(e) => index_fail(e),
+    }
 }
 
 #[inline(never)]
 #[cold]
 #[track_caller]
-fn slice_end_index_len_fail(index: usize, len: usize) -> ! {
-    panic!(
-        "range end index {} out of range for slice of length {}",
-        index, len
-    )
+fn index_fail(e: IndexError) -> ! {
+    panic!("{}", e)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::vec::Vec;
+
+    const ENDPOINTS: [usize; 8] = [0, 1, 2, 3, 4, 5, usize::MAX - 1, usize::MAX];
+
+    /// Identifies a subslice by its offset into `base` and its length.
+    fn locate<T>(base: *const T, s: &SliceExists<T>) -> (usize, usize) {
+        (
+            (s.as_ptr() as usize - base as usize) / core::mem::size_of::<T>(),
+            s.len(),
+        )
+    }
+
+    /// Runs `f`, and returns its panic message if it panics.
+    fn panic_message(f: impl FnOnce()) -> Option<std::string::String> {
+        let payload = catch_unwind(AssertUnwindSafe(f)).err()?;
+        match payload.downcast::<std::string::String>() {
+            Ok(msg) => Some(*msg),
+            Err(payload) => Some((*payload.downcast::<&str>().unwrap()).into()),
+        }
+    }
+
+    /// Checks that `get`, `get_mut`, `index` and `index_mut` behave like they do on `[T]`,
+    /// including their panic messages.
+    fn check<I>(index: I)
+    where
+        I: SliceExistsIndex<SliceExists<u32>, Output = SliceExists<u32>>
+            + core::slice::SliceIndex<[u32], Output = [u32]>
+            + Clone
+            + core::fmt::Debug,
+    {
+        for len in 0..=4 {
+            let mut buf: Vec<u32> = (0..len as u32).collect();
+            let base = buf.as_ptr();
+            let expected = buf
+                .get(index.clone())
+                .map(|s| ((s.as_ptr() as usize - base as usize) / 4, s.len()));
+            let expected_msg = panic_message(|| {
+                let _ = &buf[index.clone()];
+            });
+
+            let s = SliceExists::from_mut(&mut buf[..]);
+            let got = s.get(index.clone()).map(|s| locate(base, s));
+            assert_eq!(got, expected, "get({:?}) on len {}", index, len);
+            let got = s.get_mut(index.clone()).map(|s| locate(base, s));
+            assert_eq!(got, expected, "get_mut({:?}) on len {}", index, len);
+
+            let got = catch_unwind(AssertUnwindSafe(|| locate(base, &s[index.clone()])));
+            assert_eq!(got.ok(), expected, "index({:?}) on len {}", index, len);
+            let got = catch_unwind(AssertUnwindSafe(|| {
+                locate(base, SliceExistsIndex::index_mut(index.clone(), s))
+            }));
+            assert_eq!(got.ok(), expected, "index_mut({:?}) on len {}", index, len);
+
+            let got = panic_message(|| {
+                let _ = &s[index.clone()];
+            });
+            assert_eq!(got, expected_msg, "index({:?}) on len {}", index, len);
+            let got = panic_message(|| {
+                SliceExistsIndex::index_mut(index.clone(), s);
+            });
+            assert_eq!(got, expected_msg, "index_mut({:?}) on len {}", index, len);
+
+            let got = s.try_get(index.clone()).map(|s| locate(base, s));
+            assert_eq!(got.ok(), expected, "try_get({:?}) on len {}", index, len);
+            let got = s.try_get_mut(index.clone()).map(|s| locate(base, s));
+            assert_eq!(
+                got.ok(),
+                expected,
+                "try_get_mut({:?}) on len {}",
+                index,
+                len
+            );
+
+            if let Some(expected) = expected {
+                let got = unsafe { s.get_unchecked(index.clone()) };
+                assert_eq!(locate(base, got), expected);
+            }
+        }
+    }
+
+    fn bounds() -> impl Iterator<Item = Bound<usize>> {
+        ENDPOINTS
+            .into_iter()
+            .flat_map(|i| [Included(i), Excluded(i)])
+            .chain([Unbounded])
+    }
+
+    #[test]
+    fn usize_matches_core() {
+        for len in 0..=4 {
+            let mut buf: Vec<u32> = (0..len as u32).collect();
+            for i in ENDPOINTS {
+                let expected = buf.get(i).copied();
+                let s = SliceExists::from_mut(&mut buf[..]);
+                assert_eq!(s.get(i).map(Exists::get), expected);
+                assert_eq!(s.get_mut(i).map(|e| e.get()), expected);
+                let got = catch_unwind(AssertUnwindSafe(|| s[i].get()));
+                assert_eq!(got.ok(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn ranges_match_core() {
+        check(..);
+        for a in ENDPOINTS {
+            check(a..);
+            check(..a);
+            check(..=a);
+            for b in ENDPOINTS {
+                check(a..b);
+                check(a..=b);
+            }
+        }
+    }
+
+    #[test]
+    fn exhausted_inclusive_range_matches_core() {
+        for (a, b) in [(0, 0), (1, 1), (2, 2), (3, 3), (usize::MAX, usize::MAX)] {
+            let mut range = a..=b;
+            range.next();
+            assert!(range.is_empty());
+            check(range);
+        }
+    }
+
+    #[test]
+    fn bounds_match_core() {
+        for start in bounds() {
+            for end in bounds() {
+                check((start, end));
+            }
+        }
+    }
+
+    /// Indexes the `n`th-from-last element.
+    struct FromEnd(usize);
+
+    unsafe impl CustomIndex for FromEnd {
+        type Output<T> = Exists<T>;
+
+        fn check<T>(&self, slice: &SliceExists<T>) -> Result<(), IndexError> {
+            if self.0 < slice.len() {
+                Ok(())
+            } else {
+                Err(IndexError::new(
+                    IndexErrorKind::OutOfBounds,
+                    self.0,
+                    slice.len(),
+                ))
+            }
+        }
+
+        unsafe fn get_unchecked<T>(self, slice: &SliceExists<T>) -> &Exists<T> {
+            slice.get_unchecked(slice.len() - 1 - self.0)
+        }
+
+        unsafe fn get_unchecked_mut<T>(self, slice: &mut SliceExists<T>) -> &mut Exists<T> {
+            let len = slice.len();
+            slice.get_unchecked_mut(len - 1 - self.0)
+        }
+    }
+
+    /// Indexes the two elements starting at a position.
+    struct Pair(usize);
+
+    unsafe impl CustomIndex for Pair {
+        type Output<T> = SliceExists<T>;
+
+        fn check<T>(&self, slice: &SliceExists<T>) -> Result<(), IndexError> {
+            slice.try_get(self.0..self.0.saturating_add(2)).map(|_| ())
+        }
+
+        unsafe fn get_unchecked<T>(self, slice: &SliceExists<T>) -> &SliceExists<T> {
+            slice.get_unchecked(self.0..self.0 + 2)
+        }
+
+        unsafe fn get_unchecked_mut<T>(self, slice: &mut SliceExists<T>) -> &mut SliceExists<T> {
+            slice.get_unchecked_mut(self.0..self.0 + 2)
+        }
+    }
+
+    #[test]
+    fn custom_index() {
+        let mut x = [1, 2, 3];
+        let s = SliceExists::from_mut(&mut x);
+        assert_eq!(s[FromEnd(0)].get(), 3);
+        s[FromEnd(2)].set(10);
+        assert_eq!(s.get(FromEnd(3)).map(|e| e.get()), None);
+        assert_eq!(
+            s.try_get(FromEnd(5)).err().map(|e| e.kind()),
+            Some(IndexErrorKind::OutOfBounds)
+        );
+        assert_eq!(s[Pair(1)].len(), 2);
+        s[Pair(1)].fill(7);
+        assert!(s.get_mut(Pair(2)).is_none());
+        assert_eq!(x, [10, 7, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn custom_index_panics_with_check_error() {
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[Pair(2)];
+    }
+
+    #[test]
+    fn error_kinds() {
+        let s = SliceExists::from_ref(&[1, 2, 3]);
+        let err = |e: IndexError| (e.kind(), e.index(), e.len());
+        assert_eq!(
+            err(s.try_get(3).err().unwrap()),
+            (IndexErrorKind::OutOfBounds, 3, 3)
+        );
+        assert_eq!(
+            err(s.try_get(4..).err().unwrap()),
+            (IndexErrorKind::StartOutOfBounds, 4, 3)
+        );
+        assert_eq!(
+            err(s.try_get(..=3).err().unwrap()),
+            (IndexErrorKind::EndOutOfBounds, 3, 3)
+        );
+        assert_eq!(
+            err(s.try_get((Included(2), Excluded(1))).err().unwrap()),
+            (IndexErrorKind::StartAfterEnd { end: 1 }, 2, 3)
+        );
+        assert_eq!(
+            err(s.try_get((Excluded(usize::MAX), Unbounded)).err().unwrap()),
+            (IndexErrorKind::StartOverflow, usize::MAX, 3)
+        );
+        assert_eq!(
+            err(s.try_get(1..=usize::MAX).err().unwrap()),
+            (IndexErrorKind::EndOverflow, usize::MAX, 3)
+        );
+    }
+
+    #[test]
+    fn error_display_matches_panic() {
+        let s = SliceExists::from_ref(&[1, 2, 3]);
+        for start in bounds() {
+            for end in bounds() {
+                if let Err(e) = s.try_get((start, end)) {
+                    let payload = catch_unwind(AssertUnwindSafe(|| &s[(start, end)]))
+                        .err()
+                        .unwrap();
+                    let msg = payload.downcast_ref::<std::string::String>().unwrap();
+                    assert_eq!(*msg, std::format!("{}", e));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn usize_past_end() {
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "slice index starts at 2 but ends at 1")]
+    fn range_order() {
+        let (start, end) = (2, 1);
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[start..end];
+    }
+
+    #[test]
+    #[should_panic(expected = "range start index 4 out of range for slice of length 3")]
+    fn range_start_checked_before_order() {
+        let (start, end) = (4, 2);
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[start..end];
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn range_end() {
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[..4];
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "range end index 18446744073709551615 out of range for slice of length 3"
+    )]
+    fn inclusive_end_max() {
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[0..=usize::MAX];
+    }
+
+    #[test]
+    #[should_panic(expected = "slice index starts at 3 but ends at 2")]
+    fn inclusive_order_reports_exclusive_end() {
+        let (start, end) = (3, 1);
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[start..=end];
+    }
+
+    #[test]
+    #[should_panic(expected = "slice index starts at 2 but ends at 1")]
+    fn bounds_end_checked_before_start() {
+        let _ = &SliceExists::from_ref(&[1, 2])[(Excluded(2), Included(0))];
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 2 out of range for slice of length 2")]
+    fn bounds_excluded_start_at_end() {
+        let _ = &SliceExists::from_ref(&[1, 2])[(Excluded(2), Unbounded)];
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "range start index 18446744073709551615 out of range for slice of length 3"
+    )]
+    fn excluded_start_max() {
+        let _ = &SliceExists::from_ref(&[1, 2, 3])[(Excluded(usize::MAX), Unbounded)];
+    }
 }
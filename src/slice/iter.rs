@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use core::{marker::PhantomData, ptr::NonNull};
+use core::{iter::FusedIterator, marker::PhantomData, mem, ptr::NonNull};
 
 use crate::{slice::SliceExists, Exists};
 
@@ -93,9 +93,127 @@ impl<'a, T: 'a> Iterator for IterMut<'a, T> {
     }
 }
 
+/// An iterator that reads each element of an existential slice by value.
+///
+/// Each element is read with a raw pointer read when it is yielded, not when the
+/// iterator is created.
+///
+/// Created by [`SliceExists::values`].
+pub struct Values<'a, T> {
+    ptr: *const T,
+    len: usize,
+    _phantom: PhantomData<&'a SliceExists<T>>,
+}
+
+impl<'a, T> Values<'a, T> {
+    #[inline]
+    pub(super) fn new(slice: &'a SliceExists<T>) -> Self {
+        Self {
+            ptr: slice.as_ptr(),
+            len: slice.len(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> Iterator for Values<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            // Safety: `ptr` is in bounds of the source slice, which is valid for reads.
+            unsafe {
+                let val = self.ptr.read();
+                self.ptr = self.ptr.add(1);
+                self.len -= 1;
+                Some(val)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: Copy + 'a> DoubleEndedIterator for Values<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            // Safety: `ptr + len` is in bounds of the source slice, which is valid for reads.
+            Some(unsafe { self.ptr.add(self.len).read() })
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a> ExactSizeIterator for Values<'a, T> {}
+impl<'a, T: Copy + 'a> FusedIterator for Values<'a, T> {}
+
+/// An iterator that reads each element of another iterator of existential references by
+/// value, like [`core::iter::Copied`].
+///
+/// Created by the `values` methods of views that are not a single slice, such as
+/// [`RingExists::values`](super::RingExists::values).
+#[derive(Clone)]
+pub struct Copied<I> {
+    iter: I,
+}
+
+impl<I> Copied<I> {
+    #[inline]
+    pub(super) fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<'a, T: Copy + 'a, I: Iterator<Item = &'a Exists<T>>> Iterator for Copied<I> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(Exists::get)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: Copy + 'a, I: DoubleEndedIterator<Item = &'a Exists<T>>> DoubleEndedIterator
+    for Copied<I>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(Exists::get)
+    }
+}
+
+impl<'a, T: Copy + 'a, I: ExactSizeIterator<Item = &'a Exists<T>>> ExactSizeIterator for Copied<I> {}
+
+impl<'a, T: Copy + 'a, I: FusedIterator<Item = &'a Exists<T>>> FusedIterator for Copied<I> {}
+
+/// An iterator over an existential slice in non-overlapping chunks of `chunk_size` elements,
+/// starting at the beginning of the slice. The last chunk may be shorter.
+///
+/// Created by [`SliceExists::chunks`].
 pub struct Chunks<'a, T> {
-    pub v: &'a SliceExists<T>,
-    pub chunk_size: usize,
+    v: &'a SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, chunk_size: usize) -> Self {
+        Self { v, chunk_size }
+    }
 }
 
 impl<'a, T: 'a> Iterator for Chunks<'a, T> {
@@ -112,16 +230,789 @@ impl<'a, T: 'a> Iterator for Chunks<'a, T> {
             Some(before)
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len().div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for Chunks<'a, T> {}
+impl<'a, T: 'a> FusedIterator for Chunks<'a, T> {}
+
+/// An iterator over a mutable existential slice in non-overlapping chunks of `chunk_size`
+/// elements, starting at the beginning of the slice. The last chunk may be shorter.
+///
+/// Created by [`SliceExists::chunks_mut`].
+pub struct ChunksMut<'a, T> {
+    v: &'a mut SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> ChunksMut<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, chunk_size: usize) -> Self {
+        Self { v, chunk_size }
+    }
+}
+
+impl<'a, T: 'a> Iterator for ChunksMut<'a, T> {
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let len = core::cmp::min(self.v.len(), self.chunk_size);
+            let (before, after) = mem::take(&mut self.v).split_at_mut(len);
+            self.v = after;
+            Some(before)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len().div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for ChunksMut<'a, T> {}
+impl<'a, T: 'a> FusedIterator for ChunksMut<'a, T> {}
+
+/// An iterator over an existential slice in non-overlapping chunks of exactly `chunk_size`
+/// elements, starting at the beginning of the slice.
+///
+/// The up to `chunk_size - 1` trailing elements are not yielded, and can be retrieved with
+/// [`remainder`](ChunksExact::remainder).
+///
+/// Created by [`SliceExists::chunks_exact`].
+pub struct ChunksExact<'a, T> {
+    v: &'a SliceExists<T>,
+    rem: &'a SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> ChunksExact<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, chunk_size: usize) -> Self {
+        let rem_len = v.len() % chunk_size;
+        let (v, rem) = v.split_at(v.len() - rem_len);
+        Self { v, rem, chunk_size }
+    }
+
+    /// Returns the trailing elements that do not fill a whole chunk.
+    #[inline]
+    pub fn remainder(&self) -> &'a SliceExists<T> {
+        self.rem
+    }
+}
+
+impl<'a, T: 'a> Iterator for ChunksExact<'a, T> {
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.len() < self.chunk_size {
+            None
+        } else {
+            let (before, after) = self.v.split_at(self.chunk_size);
+            self.v = after;
+            Some(before)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len() / self.chunk_size;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for ChunksExact<'a, T> {}
+impl<'a, T: 'a> FusedIterator for ChunksExact<'a, T> {}
+
+/// An iterator over a mutable existential slice in non-overlapping chunks of exactly
+/// `chunk_size` elements, starting at the beginning of the slice.
+///
+/// The up to `chunk_size - 1` trailing elements are not yielded, and can be retrieved with
+/// [`remainder`](ChunksExactMut::remainder) or [`into_remainder`](ChunksExactMut::into_remainder).
+///
+/// Created by [`SliceExists::chunks_exact_mut`].
+pub struct ChunksExactMut<'a, T> {
+    v: &'a mut SliceExists<T>,
+    rem: &'a mut SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> ChunksExactMut<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, chunk_size: usize) -> Self {
+        let rem_len = v.len() % chunk_size;
+        let mid = v.len() - rem_len;
+        let (v, rem) = v.split_at_mut(mid);
+        Self { v, rem, chunk_size }
+    }
+
+    /// Returns the trailing elements that do not fill a whole chunk.
+    #[inline]
+    pub fn remainder(&self) -> &SliceExists<T> {
+        self.rem
+    }
+
+    /// Returns the trailing elements that do not fill a whole chunk, for the full lifetime `'a`.
+    #[inline]
+    pub fn into_remainder(self) -> &'a mut SliceExists<T> {
+        self.rem
+    }
+}
+
+impl<'a, T: 'a> Iterator for ChunksExactMut<'a, T> {
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.len() < self.chunk_size {
+            None
+        } else {
+            let (before, after) = mem::take(&mut self.v).split_at_mut(self.chunk_size);
+            self.v = after;
+            Some(before)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len() / self.chunk_size;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for ChunksExactMut<'a, T> {}
+impl<'a, T: 'a> FusedIterator for ChunksExactMut<'a, T> {}
+
+/// An iterator over an existential slice in non-overlapping chunks of `chunk_size` elements,
+/// starting at the end of the slice. The last chunk may be shorter.
+///
+/// Created by [`SliceExists::rchunks`].
+pub struct RChunks<'a, T> {
+    v: &'a SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> RChunks<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, chunk_size: usize) -> Self {
+        Self { v, chunk_size }
+    }
 }
 
+impl<'a, T: 'a> Iterator for RChunks<'a, T> {
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let len = core::cmp::min(self.v.len(), self.chunk_size);
+            let (before, after) = self.v.split_at(self.v.len() - len);
+            self.v = before;
+            Some(after)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len().div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for RChunks<'a, T> {}
+impl<'a, T: 'a> FusedIterator for RChunks<'a, T> {}
+
+/// An iterator over a mutable existential slice in non-overlapping chunks of `chunk_size`
+/// elements, starting at the end of the slice. The last chunk may be shorter.
+///
+/// Created by [`SliceExists::rchunks_mut`].
+pub struct RChunksMut<'a, T> {
+    v: &'a mut SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> RChunksMut<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, chunk_size: usize) -> Self {
+        Self { v, chunk_size }
+    }
+}
+
+impl<'a, T: 'a> Iterator for RChunksMut<'a, T> {
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let len = core::cmp::min(self.v.len(), self.chunk_size);
+            let mid = self.v.len() - len;
+            let (before, after) = mem::take(&mut self.v).split_at_mut(mid);
+            self.v = before;
+            Some(after)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len().div_ceil(self.chunk_size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for RChunksMut<'a, T> {}
+impl<'a, T: 'a> FusedIterator for RChunksMut<'a, T> {}
+
+/// An iterator over an existential slice in non-overlapping chunks of exactly `chunk_size`
+/// elements, starting at the end of the slice.
+///
+/// The up to `chunk_size - 1` leading elements are not yielded, and can be retrieved with
+/// [`remainder`](RChunksExact::remainder).
+///
+/// Created by [`SliceExists::rchunks_exact`].
+pub struct RChunksExact<'a, T> {
+    v: &'a SliceExists<T>,
+    rem: &'a SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> RChunksExact<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, chunk_size: usize) -> Self {
+        let (rem, v) = v.split_at(v.len() % chunk_size);
+        Self { v, rem, chunk_size }
+    }
+
+    /// Returns the leading elements that do not fill a whole chunk.
+    #[inline]
+    pub fn remainder(&self) -> &'a SliceExists<T> {
+        self.rem
+    }
+}
+
+impl<'a, T: 'a> Iterator for RChunksExact<'a, T> {
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.len() < self.chunk_size {
+            None
+        } else {
+            let (before, after) = self.v.split_at(self.v.len() - self.chunk_size);
+            self.v = before;
+            Some(after)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len() / self.chunk_size;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for RChunksExact<'a, T> {}
+impl<'a, T: 'a> FusedIterator for RChunksExact<'a, T> {}
+
+/// An iterator over a mutable existential slice in non-overlapping chunks of exactly
+/// `chunk_size` elements, starting at the end of the slice.
+///
+/// The up to `chunk_size - 1` leading elements are not yielded, and can be retrieved with
+/// [`remainder`](RChunksExactMut::remainder) or [`into_remainder`](RChunksExactMut::into_remainder).
+///
+/// Created by [`SliceExists::rchunks_exact_mut`].
+pub struct RChunksExactMut<'a, T> {
+    v: &'a mut SliceExists<T>,
+    rem: &'a mut SliceExists<T>,
+    chunk_size: usize,
+}
+
+impl<'a, T> RChunksExactMut<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, chunk_size: usize) -> Self {
+        let mid = v.len() % chunk_size;
+        let (rem, v) = v.split_at_mut(mid);
+        Self { v, rem, chunk_size }
+    }
+
+    /// Returns the leading elements that do not fill a whole chunk.
+    #[inline]
+    pub fn remainder(&self) -> &SliceExists<T> {
+        self.rem
+    }
+
+    /// Returns the leading elements that do not fill a whole chunk, for the full lifetime `'a`.
+    #[inline]
+    pub fn into_remainder(self) -> &'a mut SliceExists<T> {
+        self.rem
+    }
+}
+
+impl<'a, T: 'a> Iterator for RChunksExactMut<'a, T> {
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.len() < self.chunk_size {
+            None
+        } else {
+            let mid = self.v.len() - self.chunk_size;
+            let (before, after) = mem::take(&mut self.v).split_at_mut(mid);
+            self.v = before;
+            Some(after)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.v.len() / self.chunk_size;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for RChunksExactMut<'a, T> {}
+impl<'a, T: 'a> FusedIterator for RChunksExactMut<'a, T> {}
+
+/// An iterator over overlapping windows of `size` elements of an existential slice.
+///
+/// Created by [`SliceExists::windows`].
+pub struct Windows<'a, T> {
+    v: &'a SliceExists<T>,
+    size: usize,
+}
+
+impl<'a, T> Windows<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, size: usize) -> Self {
+        Self { v, size }
+    }
+}
+
+impl<'a, T: 'a> Iterator for Windows<'a, T> {
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size > self.v.len() {
+            None
+        } else {
+            let window = &self.v[..self.size];
+            self.v = &self.v[1..];
+            Some(window)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.v.len() + 1).saturating_sub(self.size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for Windows<'a, T> {}
+impl<'a, T: 'a> FusedIterator for Windows<'a, T> {}
+
+/// An iterator over overlapping mutable windows of `size` elements of an existential slice.
+///
+/// Consecutive windows share `size - 1` elements. This is sound because
+/// `&mut SliceExists<T>` does not assert uniqueness of the memory it points to,
+/// so every window yielded may be held and written to at the same time.
+///
+/// Created by [`SliceExists::windows_mut`].
+pub struct WindowsMut<'a, T> {
+    v: &'a mut SliceExists<T>,
+    size: usize,
+}
+
+impl<'a, T> WindowsMut<'a, T> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, size: usize) -> Self {
+        Self { v, size }
+    }
+}
+
+impl<'a, T: 'a> Iterator for WindowsMut<'a, T> {
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size > self.v.len() {
+            None
+        } else {
+            let [window, rest] = mem::take(&mut self.v).copy_mut();
+            self.v = &mut rest[1..];
+            Some(&mut window[..self.size])
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.v.len() + 1).saturating_sub(self.size);
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a> ExactSizeIterator for WindowsMut<'a, T> {}
+impl<'a, T: 'a> FusedIterator for WindowsMut<'a, T> {}
+
+/// Returns the length of the first run of `v` where `pred` holds for each adjacent pair.
+#[inline]
+fn chunk_by_len<T, P>(v: &SliceExists<T>, pred: &mut P) -> usize
+where
+    P: FnMut(&Exists<T>, &Exists<T>) -> bool,
+{
+    let mut len = 1;
+    while len < v.len() && pred(&v[len - 1], &v[len]) {
+        len += 1;
+    }
+    len
+}
+
+/// An iterator over an existential slice in runs of elements, separated where
+/// the predicate returns `false` for a pair of adjacent elements.
+///
+/// Created by [`SliceExists::chunk_by`].
+pub struct ChunkBy<'a, T, P> {
+    v: &'a SliceExists<T>,
+    pred: P,
+}
+
+impl<'a, T, P> ChunkBy<'a, T, P> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, pred: P) -> Self {
+        Self { v, pred }
+    }
+}
+
+impl<'a, T: 'a, P> Iterator for ChunkBy<'a, T, P>
+where
+    P: FnMut(&Exists<T>, &Exists<T>) -> bool,
+{
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let len = chunk_by_len(self.v, &mut self.pred);
+            let (before, after) = self.v.split_at(len);
+            self.v = after;
+            Some(before)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.v.len();
+        ((len != 0) as usize, Some(len))
+    }
+}
+
+impl<'a, T: 'a, P> FusedIterator for ChunkBy<'a, T, P> where P: FnMut(&Exists<T>, &Exists<T>) -> bool
+{}
+
+/// An iterator over a mutable existential slice in runs of elements, separated where
+/// the predicate returns `false` for a pair of adjacent elements.
+///
+/// Created by [`SliceExists::chunk_by_mut`].
+pub struct ChunkByMut<'a, T, P> {
+    v: &'a mut SliceExists<T>,
+    pred: P,
+}
+
+impl<'a, T, P> ChunkByMut<'a, T, P> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, pred: P) -> Self {
+        Self { v, pred }
+    }
+}
+
+impl<'a, T: 'a, P> Iterator for ChunkByMut<'a, T, P>
+where
+    P: FnMut(&Exists<T>, &Exists<T>) -> bool,
+{
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.v.is_empty() {
+            None
+        } else {
+            let len = chunk_by_len(self.v, &mut self.pred);
+            let (before, after) = mem::take(&mut self.v).split_at_mut(len);
+            self.v = after;
+            Some(before)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.v.len();
+        ((len != 0) as usize, Some(len))
+    }
+}
+
+impl<'a, T: 'a, P> FusedIterator for ChunkByMut<'a, T, P> where
+    P: FnMut(&Exists<T>, &Exists<T>) -> bool
+{
+}
+
+/// An iterator over subslices of an existential slice separated by elements
+/// matching a predicate. The matched elements are not included in the subslices.
+///
+/// Created by [`SliceExists::split`].
+pub struct Split<'a, T, P> {
+    v: &'a SliceExists<T>,
+    pred: P,
+    finished: bool,
+}
+
+impl<'a, T, P> Split<'a, T, P> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, pred: P) -> Self {
+        Self {
+            v,
+            pred,
+            finished: false,
+        }
+    }
+
+    /// Marks the iterator as finished and returns the rest of the slice.
+    #[inline]
+    fn finish(&mut self) -> Option<&'a SliceExists<T>> {
+        if self.finished {
+            None
+        } else {
+            self.finished = true;
+            Some(self.v)
+        }
+    }
+}
+
+impl<'a, T: 'a, P> Iterator for Split<'a, T, P>
+where
+    P: FnMut(&Exists<T>) -> bool,
+{
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.v.iter().position(&mut self.pred) {
+            None => self.finish(),
+            Some(idx) => {
+                let ret = &self.v[..idx];
+                self.v = &self.v[idx + 1..];
+                Some(ret)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.v.len() + 1))
+        }
+    }
+}
+
+impl<'a, T: 'a, P> FusedIterator for Split<'a, T, P> where P: FnMut(&Exists<T>) -> bool {}
+
+/// An iterator over mutable subslices of an existential slice separated by elements
+/// matching a predicate. The matched elements are not included in the subslices.
+///
+/// Created by [`SliceExists::split_mut`].
+pub struct SplitMut<'a, T, P> {
+    v: &'a mut SliceExists<T>,
+    pred: P,
+    finished: bool,
+}
+
+impl<'a, T, P> SplitMut<'a, T, P> {
+    #[inline]
+    pub(super) fn new(v: &'a mut SliceExists<T>, pred: P) -> Self {
+        Self {
+            v,
+            pred,
+            finished: false,
+        }
+    }
+}
+
+impl<'a, T: 'a, P> Iterator for SplitMut<'a, T, P>
+where
+    P: FnMut(&Exists<T>) -> bool,
+{
+    type Item = &'a mut SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let idx = self.v.iter().position(&mut self.pred);
+        let v = mem::take(&mut self.v);
+        match idx {
+            None => {
+                self.finished = true;
+                Some(v)
+            }
+            Some(idx) => {
+                let (before, after) = v.split_at_mut(idx);
+                self.v = &mut after[1..];
+                Some(before)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.v.len() + 1))
+        }
+    }
+}
+
+impl<'a, T: 'a, P> FusedIterator for SplitMut<'a, T, P> where P: FnMut(&Exists<T>) -> bool {}
+
+/// An iterator over subslices of an existential slice separated by elements
+/// matching a predicate, starting from the end of the slice.
+///
+/// Created by [`SliceExists::rsplit`].
+pub struct RSplit<'a, T, P> {
+    v: &'a SliceExists<T>,
+    pred: P,
+    finished: bool,
+}
+
+impl<'a, T, P> RSplit<'a, T, P> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, pred: P) -> Self {
+        Self {
+            v,
+            pred,
+            finished: false,
+        }
+    }
+}
+
+impl<'a, T: 'a, P> Iterator for RSplit<'a, T, P>
+where
+    P: FnMut(&Exists<T>) -> bool,
+{
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let v = self.v;
+        match (0..v.len()).rev().find(|&i| (self.pred)(&v[i])) {
+            None => {
+                self.finished = true;
+                Some(v)
+            }
+            Some(idx) => {
+                self.v = &v[..idx];
+                Some(&v[idx + 1..])
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.v.len() + 1))
+        }
+    }
+}
+
+impl<'a, T: 'a, P> FusedIterator for RSplit<'a, T, P> where P: FnMut(&Exists<T>) -> bool {}
+
+/// An iterator over at most `n` subslices of an existential slice separated by elements
+/// matching a predicate. The last subslice contains the remainder of the slice.
+///
+/// Created by [`SliceExists::splitn`].
+pub struct SplitN<'a, T, P> {
+    inner: Split<'a, T, P>,
+    count: usize,
+}
+
+impl<'a, T, P> SplitN<'a, T, P> {
+    #[inline]
+    pub(super) fn new(v: &'a SliceExists<T>, n: usize, pred: P) -> Self {
+        Self {
+            inner: Split::new(v, pred),
+            count: n,
+        }
+    }
+}
+
+impl<'a, T: 'a, P> Iterator for SplitN<'a, T, P>
+where
+    P: FnMut(&Exists<T>) -> bool,
+{
+    type Item = &'a SliceExists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.inner.finish()
+            }
+            _ => {
+                self.count -= 1;
+                self.inner.next()
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        let count = self.count;
+        (
+            core::cmp::min(lower, count),
+            upper.map(|upper| core::cmp::min(upper, count)),
+        )
+    }
+}
+
+impl<'a, T: 'a, P> FusedIterator for SplitN<'a, T, P> where P: FnMut(&Exists<T>) -> bool {}
+
 #[cfg(test)]
 mod tests {
+    extern crate alloc;
     use crate::SliceExists;
+    use alloc::vec::Vec;
 
     #[test]
     fn test_iteration() {
-        extern crate alloc;
-        use alloc::vec::Vec;
         let x = [1, 2, 3, 4, 5];
         let y: Vec<i32> = SliceExists::from_ref(&x)
             .iter()
@@ -129,4 +1020,96 @@ mod tests {
             .collect();
         assert_eq!(&y[..], &[2, 4, 6, 8, 10]);
     }
+
+    #[test]
+    fn test_values() {
+        let x = [1, 2, 3];
+        let x = SliceExists::from_ref(&x);
+        assert_eq!(x.values().len(), 3);
+        assert_eq!(x.values().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(x.values().rev().collect::<Vec<_>>(), [3, 2, 1]);
+    }
+
+    fn lens<'a, T: 'a>(it: impl Iterator<Item = &'a SliceExists<T>>) -> Vec<usize> {
+        it.map(|s| s.len()).collect()
+    }
+
+    #[test]
+    fn test_chunks() {
+        let x = [0u8; 7];
+        let x = SliceExists::from_ref(&x);
+        assert_eq!(lens(x.chunks(3)), [3, 3, 1]);
+        assert_eq!(x.chunks(3).len(), 3);
+        assert_eq!(lens(x.rchunks(3)), [3, 3, 1]);
+
+        let mut exact = x.chunks_exact(3);
+        assert_eq!(exact.len(), 2);
+        assert_eq!(exact.remainder().len(), 1);
+        assert_eq!(lens(&mut exact), [3, 3]);
+        assert_eq!(lens(x.rchunks_exact(3)), [3, 3]);
+        assert_eq!(x.rchunks_exact(3).remainder().len(), 1);
+    }
+
+    #[test]
+    fn test_chunks_mut() {
+        let mut x = [0u32; 7];
+        let s = SliceExists::from_mut(&mut x);
+        for (i, chunk) in s.chunks_mut(3).enumerate() {
+            chunk[0].set(i as u32 + 1);
+        }
+        for (i, chunk) in s.rchunks_exact_mut(2).enumerate() {
+            chunk[1].set(i as u32 + 10);
+        }
+        let mut exact = s.chunks_exact_mut(3);
+        assert_eq!(exact.by_ref().count(), 2);
+        exact.into_remainder()[0].set(3);
+        assert_eq!(x, [1, 0, 12, 2, 11, 0, 3]);
+    }
+
+    #[test]
+    fn test_windows() {
+        let x = [1, 2, 3, 4];
+        let x = SliceExists::from_ref(&x);
+        let sums: Vec<i32> = x.windows(2).map(|w| w[0].get() + w[1].get()).collect();
+        assert_eq!(sums, [3, 5, 7]);
+        assert_eq!(x.windows(5).len(), 0);
+    }
+
+    #[test]
+    fn test_windows_mut_prefix_sum() {
+        let mut x = [1, 2, 3, 4];
+        let windows: Vec<_> = SliceExists::from_mut(&mut x).windows_mut(2).collect();
+        // All windows are alive at once and overlap.
+        for w in windows {
+            let sum = w[0].get() + w[1].get();
+            w[1].set(sum);
+        }
+        assert_eq!(x, [1, 3, 6, 10]);
+    }
+
+    #[test]
+    fn test_chunk_by() {
+        let x = [1, 1, 2, 3, 3, 3];
+        let x = SliceExists::from_ref(&x);
+        assert_eq!(lens(x.chunk_by(|a, b| a.get() == b.get())), [2, 1, 3]);
+    }
+
+    #[test]
+    fn test_split() {
+        let x = [1, 0, 2, 3, 0, 0, 4];
+        let x = SliceExists::from_ref(&x);
+        assert_eq!(lens(x.split(|e| e.get() == 0)), [1, 2, 0, 1]);
+        assert_eq!(lens(x.rsplit(|e| e.get() == 0)), [1, 0, 2, 1]);
+        assert_eq!(lens(x.splitn(2, |e| e.get() == 0)), [1, 5]);
+        assert_eq!(lens(x.splitn(0, |e| e.get() == 0)), []);
+
+        let empty: &SliceExists<i32> = Default::default();
+        assert_eq!(lens(empty.split(|e| e.get() == 0)), [0]);
+
+        let mut y = [1, 0, 2];
+        for part in SliceExists::from_mut(&mut y).split_mut(|e| e.get() == 0) {
+            part[0].set(5);
+        }
+        assert_eq!(y, [5, 0, 5]);
+    }
 }
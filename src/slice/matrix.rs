@@ -0,0 +1,990 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Two-dimensional views over existential slices.
+
+use core::iter::{Flatten, FusedIterator};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut, RangeBounds};
+use core::ptr;
+
+use super::index;
+use super::{Copied, SliceExists, StridedSliceExists, StridedSliceExistsMut};
+use crate::Exists;
+
+/// A two-dimensional view of an existential slice.
+///
+/// Element `(r, c)` is located `r * row_stride() + c * col_stride()` bytes from
+/// element `(0, 0)`. A view created from a slice is row-major, with a row pitch that
+/// may exceed the number of columns, such as for padded image rows. Transposed
+/// views and columns are also matrices, with different strides.
+///
+/// When the columns are contiguous, [`row`](MatrixExists::row) returns each row as an
+/// existential slice. Columns, and the rows of a transposed view, are strided views.
+///
+/// Created by [`SliceExists::as_matrix`] and [`SliceExists::as_matrix_with_pitch`].
+pub struct MatrixExists<'a, T> {
+    ptr: *const T,
+    rows: usize,
+    cols: usize,
+    row_stride: isize,
+    col_stride: isize,
+    _phantom: PhantomData<&'a SliceExists<T>>,
+}
+
+/// A mutable two-dimensional view of an existential slice.
+///
+/// Like `&mut SliceExists<T>`, several mutable matrix views may overlap, and
+/// [`MatrixExistsMut::copy_mut`] creates them without any unsafe code.
+///
+/// Created by [`SliceExists::as_matrix_mut`] and [`SliceExists::as_matrix_with_pitch_mut`].
+pub struct MatrixExistsMut<'a, T> {
+    ptr: *mut T,
+    rows: usize,
+    cols: usize,
+    row_stride: isize,
+    col_stride: isize,
+    _phantom: PhantomData<&'a mut SliceExists<T>>,
+}
+
+/// Checks that a `rows` by `cols` matrix with row pitch `pitch` fits in `len` elements,
+/// and returns its row stride in bytes.
+///
+/// The row pitch of a matrix with at most one non-empty row is never used, so it is
+/// replaced by one that cannot overflow.
+#[inline]
+#[track_caller]
+fn checked_row_stride<T>(rows: usize, cols: usize, pitch: usize, len: usize) -> isize {
+    let needed = match (rows, cols) {
+        (0, _) | (_, 0) => Some(0),
+        _ => (rows - 1)
+            .checked_mul(pitch)
+            .and_then(|n| n.checked_add(cols)),
+    };
+    if !matches!(needed, Some(n) if n <= len) {
+        matrix_fit_fail(rows, cols, pitch, len);
+    }
+    let pitch = if rows > 1 && cols > 0 {
+        pitch
+    } else {
+        cols.min(len)
+    };
+    pitch as isize * mem::size_of::<T>() as isize
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn matrix_fit_fail(rows: usize, cols: usize, pitch: usize, len: usize) -> ! {
+    panic!(
+        "{}x{} matrix with pitch {} does not fit in a slice of length {}",
+        rows, cols, pitch, len
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn matrix_row_fail(col_stride: isize) -> ! {
+    panic!(
+        "matrix rows are not contiguous (column stride is {} bytes)",
+        col_stride
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn matrix_index_fail(index: (usize, usize), rows: usize, cols: usize) -> ! {
+    panic!(
+        "index {:?} out of bounds for a {}x{} matrix",
+        index, rows, cols
+    )
+}
+
+/// Returns the byte offset of element `(r, c)`.
+#[inline]
+fn offset(r: usize, c: usize, row_stride: isize, col_stride: isize) -> isize {
+    r as isize * row_stride + c as isize * col_stride
+}
+
+impl<'a, T> MatrixExists<'a, T> {
+    /// Constructs a matrix view from its raw parts.
+    ///
+    /// # Safety
+    /// For every `r < rows` and `c < cols`, the element `r * row_stride + c * col_stride`
+    /// bytes from `data` must meet the requirements of [`Exists::from_ptr`] for lifetime
+    /// `'a`, and all of these elements must be within a single allocated object.
+    #[inline]
+    pub unsafe fn from_raw_parts(
+        data: *const T,
+        rows: usize,
+        cols: usize,
+        row_stride: isize,
+        col_stride: isize,
+    ) -> Self {
+        Self {
+            ptr: data,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the distance between consecutive rows, in bytes.
+    #[inline]
+    pub fn row_stride(&self) -> isize {
+        self.row_stride
+    }
+
+    /// Returns the distance between consecutive columns, in bytes.
+    #[inline]
+    pub fn col_stride(&self) -> isize {
+        self.col_stride
+    }
+
+    /// Returns `true` if the matrix has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0 || self.cols == 0
+    }
+
+    /// Returns a raw pointer to element `(0, 0)`.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns an existential reference to the element at row `r` and column `c`,
+    /// or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, r: usize, c: usize) -> Option<&'a Exists<T>> {
+        // Safety: `r` and `c` were just checked.
+        (r < self.rows && c < self.cols).then(|| unsafe { self.get_unchecked(r, c) })
+    }
+
+    /// Returns an existential reference to the element at row `r` and column `c`,
+    /// without doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the result is unused.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, r: usize, c: usize) -> &'a Exists<T> {
+        Exists::from_ptr(
+            self.ptr
+                .byte_offset(offset(r, c, self.row_stride, self.col_stride)),
+        )
+    }
+
+    /// Returns row `r` as an existential slice.
+    ///
+    /// # Panics
+    /// Panics if `r >= rows`, or if the columns are not contiguous, such as in a
+    /// transposed view. [`row_strided`](MatrixExists::row_strided) accepts any view.
+    #[inline]
+    #[track_caller]
+    pub fn row(&self, r: usize) -> &'a SliceExists<T> {
+        if r >= self.rows {
+            matrix_index_fail((r, 0), self.rows, self.cols);
+        }
+        match self.get_row(r) {
+            Some(row) => row,
+            None => matrix_row_fail(self.col_stride),
+        }
+    }
+
+    /// Returns row `r` as an existential slice, or `None` if `r` is out of bounds or
+    /// the columns are not contiguous, such as in a transposed view.
+    #[inline]
+    pub fn get_row(&self, r: usize) -> Option<&'a SliceExists<T>> {
+        if r >= self.rows || (self.cols > 1 && self.col_stride != mem::size_of::<T>() as isize) {
+            return None;
+        }
+        // Safety: `r` was just checked, and the row is contiguous. A row with no columns
+        // may start past the end of the buffer, so its address is computed with wrapping.
+        unsafe {
+            let data = self.ptr.wrapping_byte_offset(r as isize * self.row_stride);
+            Some(SliceExists::from_ptr(ptr::slice_from_raw_parts(
+                data, self.cols,
+            )))
+        }
+    }
+
+    /// Returns row `r` as a strided view, whether or not the columns are contiguous.
+    ///
+    /// # Panics
+    /// Panics if `r >= rows`.
+    #[inline]
+    #[track_caller]
+    pub fn row_strided(&self, r: usize) -> StridedSliceExists<'a, T> {
+        if r >= self.rows {
+            matrix_index_fail((r, 0), self.rows, self.cols);
+        }
+        // Safety: `r` was just checked, and the row's elements are elements of `self`.
+        // See `get_row` for why the address is computed with wrapping.
+        unsafe {
+            StridedSliceExists::from_raw_parts(
+                self.ptr.wrapping_byte_offset(r as isize * self.row_stride),
+                self.cols,
+                self.col_stride,
+            )
+        }
+    }
+
+    /// Returns column `c` as a strided view.
+    ///
+    /// # Panics
+    /// Panics if `c >= cols`.
+    #[inline]
+    #[track_caller]
+    pub fn col(&self, c: usize) -> StridedSliceExists<'a, T> {
+        if c >= self.cols {
+            matrix_index_fail((0, c), self.rows, self.cols);
+        }
+        self.transpose().row_strided(c)
+    }
+
+    /// Returns the sub-matrix covering the given ranges of rows and columns.
+    ///
+    /// # Panics
+    /// Panics if either range is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn sub_matrix<R, C>(self, rows: R, cols: C) -> Self
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let rows = index::range(rows, self.rows);
+        let cols = index::range(cols, self.cols);
+        let start = offset(rows.start, cols.start, self.row_stride, self.col_stride);
+        // Safety: the new elements are a subset of the old ones. An empty sub-matrix
+        // may start past the end of the buffer, so its address is computed with wrapping.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr.wrapping_byte_offset(start),
+                rows.len(),
+                cols.len(),
+                self.row_stride,
+                self.col_stride,
+            )
+        }
+    }
+
+    /// Returns the transposed view of the matrix, whose rows are this matrix's columns.
+    #[inline]
+    pub fn transpose(self) -> Self {
+        // Safety: the elements are the same as the old ones.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr,
+                self.cols,
+                self.rows,
+                self.col_stride,
+                self.row_stride,
+            )
+        }
+    }
+
+    /// Returns an iterator over the rows of the matrix, as strided views.
+    #[inline]
+    pub fn iter_rows(&self) -> Rows<'a, T> {
+        Rows { m: *self }
+    }
+
+    /// Returns an iterator over the elements of the matrix, in row-major order.
+    #[inline]
+    pub fn iter(&self) -> Flatten<Rows<'a, T>> {
+        self.iter_rows().flatten()
+    }
+}
+
+impl<'a, T: Copy> MatrixExists<'a, T> {
+    /// Returns an iterator that reads each element of the matrix by value, in
+    /// row-major order.
+    #[inline]
+    pub fn values(&self) -> Copied<Flatten<Rows<'a, T>>> {
+        Copied::new(self.iter())
+    }
+}
+
+impl<T> Clone for MatrixExists<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MatrixExists<'_, T> {}
+
+impl<T> Index<(usize, usize)> for MatrixExists<'_, T> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, (r, c): (usize, usize)) -> &Exists<T> {
+        match self.get(r, c) {
+            Some(e) => e,
+            None => matrix_index_fail((r, c), self.rows, self.cols),
+        }
+    }
+}
+
+impl<'a, T> MatrixExistsMut<'a, T> {
+    /// Constructs a mutable matrix view from its raw parts.
+    ///
+    /// # Safety
+    /// For every `r < rows` and `c < cols`, the element `r * row_stride + c * col_stride`
+    /// bytes from `data` must meet the requirements of [`Exists::from_mut_ptr`] for
+    /// lifetime `'a`, and all of these elements must be within a single allocated object.
+    #[inline]
+    pub unsafe fn from_raw_parts(
+        data: *mut T,
+        rows: usize,
+        cols: usize,
+        row_stride: isize,
+        col_stride: isize,
+    ) -> Self {
+        Self {
+            ptr: data,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the distance between consecutive rows, in bytes.
+    #[inline]
+    pub fn row_stride(&self) -> isize {
+        self.row_stride
+    }
+
+    /// Returns the distance between consecutive columns, in bytes.
+    #[inline]
+    pub fn col_stride(&self) -> isize {
+        self.col_stride
+    }
+
+    /// Returns `true` if the matrix has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0 || self.cols == 0
+    }
+
+    /// Returns a raw mutable pointer to element `(0, 0)`.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns a shared view of the same elements.
+    #[inline]
+    pub fn as_matrix(&self) -> MatrixExists<'_, T> {
+        // Safety: the elements are valid for writes, and so also for reads.
+        unsafe {
+            MatrixExists::from_raw_parts(
+                self.ptr,
+                self.rows,
+                self.cols,
+                self.row_stride,
+                self.col_stride,
+            )
+        }
+    }
+
+    /// Reborrows the view, so that it can be passed on without being consumed.
+    #[inline]
+    pub fn reborrow(&mut self) -> MatrixExistsMut<'_, T> {
+        // Safety: the copy is only usable for as long as `self` is borrowed.
+        unsafe { self.alias() }
+    }
+
+    /// Safely copies this mutable view into multiple identical views.
+    ///
+    /// Since this type does not assert aliasing of pointed memory, this can be done safely.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4];
+    /// let mut m = SliceExists::from_mut(&mut x).as_matrix_mut(2, 2);
+    /// let [mut a, b] = m.copy_mut();
+    /// // Copy the first row over the second, through an overlapping view.
+    /// a.sub_matrix_mut(1.., ..).copy_from(b.as_matrix().sub_matrix(..1, ..));
+    /// assert_eq!(x, [1, 2, 1, 2]);
+    /// ```
+    #[inline]
+    pub fn copy_mut<const N: usize>(&mut self) -> [MatrixExistsMut<'_, T>; N] {
+        // Safety: each copy is only usable for as long as `self` is borrowed.
+        [(); N].map(|()| unsafe { self.alias() })
+    }
+
+    /// Returns an existential reference to the element at row `r` and column `c`,
+    /// or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, r: usize, c: usize) -> Option<&Exists<T>> {
+        self.as_matrix().get(r, c)
+    }
+
+    /// Returns a mutable existential reference to the element at row `r` and column `c`,
+    /// or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, r: usize, c: usize) -> Option<&mut Exists<T>> {
+        // Safety: `r` and `c` were just checked.
+        (r < self.rows && c < self.cols).then(|| unsafe { self.get_unchecked_mut(r, c) })
+    }
+
+    /// Returns a mutable existential reference to the element at row `r` and column `c`,
+    /// without doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the result is unused.
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, r: usize, c: usize) -> &mut Exists<T> {
+        Exists::from_mut_ptr(
+            self.ptr
+                .byte_offset(offset(r, c, self.row_stride, self.col_stride)),
+        )
+    }
+
+    /// Returns row `r` as a mutable existential slice.
+    ///
+    /// # Panics
+    /// Panics if `r >= rows`, or if the columns are not contiguous, such as in a
+    /// transposed view. [`row_strided_mut`](MatrixExistsMut::row_strided_mut) accepts
+    /// any view.
+    #[inline]
+    #[track_caller]
+    pub fn row_mut(&mut self, r: usize) -> &mut SliceExists<T> {
+        let row = self.as_matrix().row(r);
+        // Safety: `self` is valid for writes.
+        unsafe { row.assume_mutable() }
+    }
+
+    /// Returns row `r` as a mutable existential slice, or `None` if `r` is out of bounds
+    /// or the columns are not contiguous, such as in a transposed view.
+    #[inline]
+    pub fn get_row_mut(&mut self, r: usize) -> Option<&mut SliceExists<T>> {
+        let row = self.as_matrix().get_row(r)?;
+        // Safety: `self` is valid for writes.
+        Some(unsafe { row.assume_mutable() })
+    }
+
+    /// Returns row `r` as a mutable strided view, whether or not the columns are
+    /// contiguous.
+    ///
+    /// # Panics
+    /// Panics if `r >= rows`.
+    #[inline]
+    #[track_caller]
+    pub fn row_strided_mut(&mut self, r: usize) -> StridedSliceExistsMut<'_, T> {
+        self.reborrow().into_row_strided(r)
+    }
+
+    /// Returns column `c` as a mutable strided view.
+    ///
+    /// # Panics
+    /// Panics if `c >= cols`.
+    #[inline]
+    #[track_caller]
+    pub fn col_mut(&mut self, c: usize) -> StridedSliceExistsMut<'_, T> {
+        if c >= self.cols {
+            matrix_index_fail((0, c), self.rows, self.cols);
+        }
+        self.reborrow().transpose().into_row_strided(c)
+    }
+
+    /// Returns the mutable sub-matrix covering the given ranges of rows and columns.
+    ///
+    /// # Panics
+    /// Panics if either range is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn sub_matrix_mut<R, C>(&mut self, rows: R, cols: C) -> MatrixExistsMut<'_, T>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let sub = self.as_matrix().sub_matrix(rows, cols);
+        // Safety: the new elements are a subset of the old ones.
+        unsafe {
+            MatrixExistsMut::from_raw_parts(
+                sub.ptr as *mut T,
+                sub.rows,
+                sub.cols,
+                sub.row_stride,
+                sub.col_stride,
+            )
+        }
+    }
+
+    /// Returns the transposed view of the matrix, whose rows are this matrix's columns.
+    #[inline]
+    pub fn transpose(self) -> Self {
+        // Safety: the elements are the same as the old ones.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr,
+                self.cols,
+                self.rows,
+                self.col_stride,
+                self.row_stride,
+            )
+        }
+    }
+
+    /// Returns an iterator over the rows of the matrix, as mutable strided views.
+    #[inline]
+    pub fn iter_rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut { m: self.reborrow() }
+    }
+
+    /// Returns an iterator over mutable existential references to the elements of the
+    /// matrix, in row-major order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Flatten<RowsMut<'_, T>> {
+        self.iter_rows_mut().flatten()
+    }
+
+    /// Returns a copy of this view with the same lifetime.
+    ///
+    /// # Safety
+    /// Mutable existential views may alias, but the copy must not outlive the
+    /// elements `self` was created from.
+    #[inline]
+    unsafe fn alias(&self) -> MatrixExistsMut<'a, T> {
+        MatrixExistsMut::from_raw_parts(
+            self.ptr,
+            self.rows,
+            self.cols,
+            self.row_stride,
+            self.col_stride,
+        )
+    }
+
+    #[inline]
+    #[track_caller]
+    fn into_row_strided(self, r: usize) -> StridedSliceExistsMut<'a, T> {
+        if r >= self.rows {
+            matrix_index_fail((r, 0), self.rows, self.cols);
+        }
+        // Safety: `r` was just checked, and the row's elements are elements of `self`.
+        // See `get_row` for why the address is computed with wrapping.
+        unsafe {
+            StridedSliceExistsMut::from_raw_parts(
+                self.ptr.wrapping_byte_offset(r as isize * self.row_stride),
+                self.cols,
+                self.col_stride,
+            )
+        }
+    }
+}
+
+impl<T: Copy> MatrixExistsMut<'_, T> {
+    /// Returns an iterator that reads each element of the matrix by value, in
+    /// row-major order.
+    #[inline]
+    pub fn values(&self) -> Copied<Flatten<Rows<'_, T>>> {
+        self.as_matrix().values()
+    }
+
+    /// Copies all elements from `src` into this matrix, in row-major order.
+    ///
+    /// The elements are copied one at a time, so `src` may overlap this matrix.
+    ///
+    /// # Panics
+    /// Panics if the two matrices have different dimensions.
+    #[track_caller]
+    pub fn copy_from(&mut self, src: MatrixExists<'_, T>) {
+        assert!(
+            (self.rows, self.cols) == (src.rows, src.cols),
+            "source matrix dimensions ({}x{}) do not match destination matrix dimensions ({}x{})",
+            src.rows,
+            src.cols,
+            self.rows,
+            self.cols,
+        );
+        for (d, s) in self.iter_mut().zip(src.iter()) {
+            d.set(s.get());
+        }
+    }
+
+    /// Fills the matrix with `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        for e in self.iter_mut() {
+            e.set(value);
+        }
+    }
+}
+
+impl<T> MatrixExistsMut<'_, T> {
+    /// Transposes a square matrix in place.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    /// SliceExists::from_mut(&mut x).as_matrix_mut(3, 3).transpose_in_place();
+    /// assert_eq!(x, [1, 4, 7, 2, 5, 8, 3, 6, 9]);
+    /// ```
+    #[track_caller]
+    pub fn transpose_in_place(&mut self) {
+        assert!(
+            self.rows == self.cols,
+            "cannot transpose a {}x{} matrix in place",
+            self.rows,
+            self.cols
+        );
+        let [mut a, b] = self.copy_mut();
+        let mut b = b.transpose();
+        for r in 0..a.rows {
+            for c in r + 1..a.cols {
+                a[(r, c)].swap(&mut b[(r, c)]);
+            }
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixExistsMut<'_, T> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, (r, c): (usize, usize)) -> &Exists<T> {
+        match self.get(r, c) {
+            Some(e) => e,
+            None => matrix_index_fail((r, c), self.rows, self.cols),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for MatrixExistsMut<'_, T> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut Exists<T> {
+        let (rows, cols) = (self.rows, self.cols);
+        match self.get_mut(r, c) {
+            Some(e) => e,
+            None => matrix_index_fail((r, c), rows, cols),
+        }
+    }
+}
+
+/// An iterator over the rows of a matrix.
+///
+/// Created by [`MatrixExists::iter_rows`].
+pub struct Rows<'a, T> {
+    m: MatrixExists<'a, T>,
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+    type Item = StridedSliceExists<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.m.rows == 0 {
+            return None;
+        }
+        let row = self.m.row_strided(0);
+        // The pointer may step past the end of the buffer here, but is never used again
+        // if so, since the matrix is then empty.
+        self.m.ptr = self.m.ptr.wrapping_byte_offset(self.m.row_stride);
+        self.m.rows -= 1;
+        Some(row)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.m.rows, Some(self.m.rows))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Rows<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.m.rows = self.m.rows.checked_sub(1)?;
+        let mut m = self.m;
+        m.rows += 1;
+        Some(m.row_strided(self.m.rows))
+    }
+}
+
+impl<T> ExactSizeIterator for Rows<'_, T> {}
+impl<T> FusedIterator for Rows<'_, T> {}
+
+/// An iterator over the rows of a mutable matrix.
+///
+/// Created by [`MatrixExistsMut::iter_rows_mut`].
+pub struct RowsMut<'a, T> {
+    m: MatrixExistsMut<'a, T>,
+}
+
+impl<'a, T> Iterator for RowsMut<'a, T> {
+    type Item = StridedSliceExistsMut<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.m.rows == 0 {
+            return None;
+        }
+        // Safety: the row lives as long as the matrix it was taken from.
+        let first = unsafe { self.m.alias() }.into_row_strided(0);
+        // The pointer may step past the end of the buffer here, but is never used again
+        // if so, since the matrix is then empty.
+        self.m.ptr = self.m.ptr.wrapping_byte_offset(self.m.row_stride);
+        self.m.rows -= 1;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.m.rows, Some(self.m.rows))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RowsMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.m.rows = self.m.rows.checked_sub(1)?;
+        // Safety: the row lives as long as the matrix it was taken from.
+        let mut m = unsafe { self.m.alias() };
+        m.rows += 1;
+        Some(m.into_row_strided(self.m.rows))
+    }
+}
+
+impl<T> ExactSizeIterator for RowsMut<'_, T> {}
+impl<T> FusedIterator for RowsMut<'_, T> {}
+
+impl<T> SliceExists<T> {
+    /// Views the slice as a row-major matrix with `rows` rows of `cols` elements.
+    ///
+    /// # Panics
+    /// Panics if the matrix does not fit in the slice.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1, 2, 3, 4, 5, 6];
+    /// let m = SliceExists::from_ref(&x).as_matrix(2, 3);
+    /// assert_eq!(m[(1, 0)].get(), 4);
+    /// assert_eq!(m.col(2).values().collect::<Vec<_>>(), [3, 6]);
+    /// assert_eq!(m.transpose()[(2, 1)].get(), 6);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn as_matrix(&self, rows: usize, cols: usize) -> MatrixExists<'_, T> {
+        self.as_matrix_with_pitch(rows, cols, cols)
+    }
+
+    /// Views the slice as a row-major matrix with `rows` rows of `cols` elements,
+    /// where each row starts `pitch` elements after the previous one.
+    ///
+    /// # Panics
+    /// Panics if the matrix does not fit in the slice.
+    #[inline]
+    #[track_caller]
+    pub fn as_matrix_with_pitch(
+        &self,
+        rows: usize,
+        cols: usize,
+        pitch: usize,
+    ) -> MatrixExists<'_, T> {
+        let row_stride = checked_row_stride::<T>(rows, cols, pitch, self.len());
+        let size = mem::size_of::<T>() as isize;
+        // Safety: every element of the matrix was just checked to be an element of `self`.
+        unsafe { MatrixExists::from_raw_parts(self.as_ptr(), rows, cols, row_stride, size) }
+    }
+
+    /// Views the slice as a mutable row-major matrix with `rows` rows of `cols` elements.
+    ///
+    /// # Panics
+    /// Panics if the matrix does not fit in the slice.
+    #[inline]
+    #[track_caller]
+    pub fn as_matrix_mut(&mut self, rows: usize, cols: usize) -> MatrixExistsMut<'_, T> {
+        self.as_matrix_with_pitch_mut(rows, cols, cols)
+    }
+
+    /// Views the slice as a mutable row-major matrix with `rows` rows of `cols` elements,
+    /// where each row starts `pitch` elements after the previous one.
+    ///
+    /// # Panics
+    /// Panics if the matrix does not fit in the slice.
+    #[inline]
+    #[track_caller]
+    pub fn as_matrix_with_pitch_mut(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        pitch: usize,
+    ) -> MatrixExistsMut<'_, T> {
+        let row_stride = checked_row_stride::<T>(rows, cols, pitch, self.len());
+        let size = mem::size_of::<T>() as isize;
+        // Safety: every element of the matrix was just checked to be an element of `self`.
+        unsafe { MatrixExistsMut::from_raw_parts(self.as_mut_ptr(), rows, cols, row_stride, size) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn pitch() {
+        // A 2x3 image with one padding element per row.
+        let x = [1, 2, 3, 0, 4, 5, 6, 0];
+        let m = SliceExists::from_ref(&x).as_matrix_with_pitch(2, 3, 4);
+        assert!(m.values().eq([1, 2, 3, 4, 5, 6]));
+        assert!(m.col(1).values().eq([2, 5]));
+        assert!(m.row(1).values().eq([4, 5, 6]));
+        assert!(m.get_row(2).is_none());
+        assert!(m.transpose().get_row(0).is_none());
+        assert!(m.transpose().row_strided(0).values().eq([1, 4]));
+        assert!(m.get(0, 3).is_none() && m.get(2, 0).is_none());
+        // The last row does not need its padding.
+        let m = SliceExists::from_ref(&x[..7]).as_matrix_with_pitch(2, 3, 4);
+        assert_eq!(m[(1, 2)].get(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "2x3 matrix with pitch 4 does not fit in a slice of length 6")]
+    fn does_not_fit() {
+        SliceExists::from_ref(&[0; 6]).as_matrix_with_pitch(2, 3, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (1, 2) out of bounds for a 2x2 matrix")]
+    fn index_out_of_bounds() {
+        let _ = &SliceExists::from_ref(&[0; 4]).as_matrix(2, 2)[(1, 2)];
+    }
+
+    #[test]
+    #[should_panic(expected = "index (0, 3) out of bounds for a 2x3 matrix")]
+    fn col_out_of_bounds() {
+        SliceExists::from_ref(&[0; 6]).as_matrix(2, 3).col(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix rows are not contiguous (column stride is 12 bytes)")]
+    fn transposed_row_is_not_a_slice() {
+        SliceExists::from_ref(&[0u32; 6])
+            .as_matrix(2, 3)
+            .transpose()
+            .row(0);
+    }
+
+    #[test]
+    fn empty_rows_past_the_end() {
+        // The sub-matrix has no columns and starts one past the end of the buffer.
+        let mut x = [0u32; 4];
+        let m = SliceExists::from_ref(&x).as_matrix(2, 2);
+        let sub = m.transpose().sub_matrix(.., 2..);
+        assert_eq!((sub.rows(), sub.cols()), (2, 0));
+        assert!(sub.get_row(1).unwrap().is_empty());
+        assert!(sub.row(1).is_empty());
+        assert!(sub.row_strided(1).is_empty());
+
+        let mut m = SliceExists::from_mut(&mut x).as_matrix_mut(2, 2);
+        let mut sub = m.reborrow().transpose();
+        let mut sub = sub.sub_matrix_mut(.., 2..);
+        assert!(sub.get_row_mut(1).unwrap().is_empty());
+        assert!(sub.row_strided_mut(1).is_empty());
+    }
+
+    #[test]
+    fn sub_matrix_and_transpose() {
+        let x: Vec<u32> = (0..12).collect();
+        let m = SliceExists::from_ref(&x).as_matrix(3, 4);
+        let sub = m.sub_matrix(1.., 1..3);
+        assert_eq!((sub.rows(), sub.cols()), (2, 2));
+        assert!(sub.values().eq([5, 6, 9, 10]));
+        assert!(sub.transpose().values().eq([5, 9, 6, 10]));
+        assert!(m.transpose().row_strided(3).values().eq([3, 7, 11]));
+        assert!(m.sub_matrix(3.., ..).is_empty());
+        let rows: Vec<Vec<u32>> = m.iter_rows().rev().map(|r| r.values().collect()).collect();
+        assert_eq!(rows, [[8, 9, 10, 11], [4, 5, 6, 7], [0, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn mutate() {
+        let mut x = [0u8; 9];
+        let mut m = SliceExists::from_mut(&mut x).as_matrix_mut(3, 3);
+        for (r, mut row) in m.iter_rows_mut().enumerate() {
+            row.fill(r as u8);
+        }
+        m.col_mut(2).fill(9);
+        m[(0, 0)].set(7);
+        m.sub_matrix_mut(1.., ..1).transpose().fill(5);
+        m.row_mut(2)[1].set(8);
+        assert_eq!(x, [7, 0, 9, 5, 1, 9, 5, 8, 9]);
+    }
+
+    #[test]
+    fn transpose_in_place() {
+        let mut x: Vec<u32> = (0..16).collect();
+        let mut m = SliceExists::from_mut(&mut x).as_matrix_mut(4, 4);
+        m.transpose_in_place();
+        m.sub_matrix_mut(1..3, 1..3).transpose_in_place();
+        assert_eq!(x, [0, 4, 8, 12, 1, 5, 6, 13, 2, 9, 10, 14, 3, 7, 11, 15]);
+    }
+
+    #[test]
+    fn overlapping_views() {
+        // Shift every row of a matrix down by one, through two aliasing views.
+        let mut x = [1, 2, 3, 4, 5, 6];
+        let mut m = SliceExists::from_mut(&mut x).as_matrix_mut(3, 2);
+        let [mut dst, src] = m.copy_mut();
+        let src = src.as_matrix().sub_matrix(..2, ..).iter_rows().rev();
+        for (mut d, s) in dst.iter_rows_mut().rev().zip(src) {
+            d.copy_from(SliceExists::from_ref(&[s[0].get(), s[1].get()]));
+        }
+        assert_eq!(x, [1, 2, 1, 2, 3, 4]);
+    }
+}
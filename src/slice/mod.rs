@@ -20,15 +20,43 @@
 //!
 
 use core::cell::Cell;
+use core::cmp::Ordering;
 use core::ops::Index;
 use core::ops::IndexMut;
-use core::ptr;
+use core::ops::{self, RangeBounds};
+use core::{mem, ptr};
 
 use crate::Exists;
 
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+mod bits;
+mod bytes;
+mod chain;
+mod cursor;
 mod index;
 mod iter;
-pub use index::SliceExistsIndex;
+mod matrix;
+mod nd;
+mod ring;
+mod sort;
+mod strided;
+pub use bits::{BitOrder, BitSliceExists, BitSliceExistsMut, BitStore, IterOnes, Lsb0, Msb0};
+pub use chain::{ChainIter, ChainIterMut, Segments, SegmentsMut, SliceExistsChain};
+#[cfg(feature = "std")]
+pub use cursor::BufExistsCursor;
+pub use cursor::ExistsCursor;
+pub use index::{CustomIndex, IndexError, IndexErrorKind, SliceExistsIndex};
+pub use iter::{
+    ChunkBy, ChunkByMut, Chunks, ChunksExact, ChunksExactMut, ChunksMut, Copied, Iter, IterMut,
+    RChunks, RChunksExact, RChunksExactMut, RChunksMut, RSplit, Split, SplitMut, SplitN, Values,
+    Windows, WindowsMut,
+};
+pub use matrix::{MatrixExists, MatrixExistsMut, Rows, RowsMut};
+pub use nd::{NdExists, NdExistsMut, NdIter, NdIterMut};
+pub use ring::{RingExists, RingIter, RingIterMut};
+pub use strided::{StridedIter, StridedIterMut, StridedSliceExists, StridedSliceExistsMut};
 
 /// A DST marker that indicates a `[T]` is accessible at this location.
 ///
@@ -124,6 +152,53 @@ impl<T> SliceExists<T> {
         &mut *(data as *mut Self)
     }
 
+    /// Constructs an existential slice reference covering `[begin, end)`.
+    ///
+    /// This mirrors [`core::slice::from_ptr_range`]. `end` is exclusive and only its
+    /// address is used, so it may be a one-past-the-end handle that is never read.
+    ///
+    /// # Safety
+    /// - `begin` and `end` must be derived from the same allocated object.
+    /// - `end` must not be before `begin`, and the distance between them must be a
+    ///   multiple of `size_of::<T>()`.
+    /// - The range must meet the requirements of [`SliceExists::from_ptr`] for lifetime `'a`.
+    ///
+    /// # Panics
+    /// Panics if `T` is zero-sized.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1u32, 2, 3, 4];
+    /// let s = SliceExists::from_ref(&x);
+    /// let (begin, end) = (&s[1], &s[3]);
+    /// let mid = unsafe { SliceExists::from_range(begin, end) };
+    /// assert_eq!(mid.values().collect::<Vec<_>>(), [2, 3]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub unsafe fn from_range<'a>(begin: &'a Exists<T>, end: &Exists<T>) -> &'a Self {
+        assert!(mem::size_of::<T>() != 0, "from_range of zero-sized type");
+        let len = end.as_ptr().offset_from(begin.as_ptr()) as usize;
+        Self::from_ptr(ptr::slice_from_raw_parts(begin.as_ptr(), len))
+    }
+
+    /// Constructs a mutable existential slice reference covering `[begin, end)`.
+    ///
+    /// # Safety
+    /// In addition to the requirements of [`SliceExists::from_range`], the range must
+    /// meet the requirements of [`SliceExists::from_mut_ptr`] for lifetime `'a`.
+    ///
+    /// # Panics
+    /// Panics if `T` is zero-sized.
+    #[inline]
+    #[track_caller]
+    pub unsafe fn from_range_mut<'a>(begin: &'a mut Exists<T>, end: &Exists<T>) -> &'a mut Self {
+        assert!(mem::size_of::<T>() != 0, "from_range of zero-sized type");
+        let len = end.as_ptr().offset_from(begin.as_ptr()) as usize;
+        Self::from_mut_ptr(ptr::slice_from_raw_parts_mut(begin.as_mut_ptr(), len))
+    }
+
     /// Asserts that the memory pointed to by `&self` can be written to, enabling
     /// safe mutating operations.
     ///
@@ -135,7 +210,9 @@ impl<T> SliceExists<T> {
     ///
     /// If the result does perform any writes, this function will not cause UB.
     #[inline]
+    #[allow(clippy::mut_from_ref, invalid_reference_casting)]
     pub unsafe fn assume_mutable(&self) -> &mut Self {
+        // `SliceExists<T>` is a slice of ZSTs, so the `&mut Self` covers no bytes.
         &mut *(self as *const Self as *mut Self)
     }
 
@@ -163,19 +240,19 @@ impl<T> SliceExists<T> {
     /// This does not perform any reads on the buffer.
     #[inline]
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.as_exists_slice().len()
     }
 
     /// Returns a raw pointer to first element in the slice's buffer.
     #[inline]
     pub fn as_ptr(&self) -> *const T {
-        self.0.as_ptr() as *const T
+        self.as_exists_slice().as_ptr() as *const T
     }
 
     /// Returns a raw mutable pointer to the first element in the slice's buffer.
     #[inline]
     pub fn as_mut_ptr(&mut self) -> *mut T {
-        self.0.as_mut_ptr() as *mut T
+        self.as_exists_slice_mut().as_mut_ptr() as *mut T
     }
 
     #[inline]
@@ -183,6 +260,151 @@ impl<T> SliceExists<T> {
         ptr::slice_from_raw_parts(self.as_ptr(), self.len())
     }
 
+    /// Returns the underlying slice of ZST markers.
+    ///
+    /// This is crate-internal because core computes every element and subslice address of
+    /// `[Exists<T>]` with a stride of 0: `&e[i]` and `&e[i..]` all point at the *start* of
+    /// the buffer, so a safe caller would be handed existential references to the wrong
+    /// elements. Only the length and start address of the returned slice are meaningful.
+    #[inline]
+    pub(crate) fn as_exists_slice(&self) -> &[Exists<T>] {
+        &self.0
+    }
+
+    /// Returns the underlying mutable slice of ZST markers.
+    ///
+    /// See [`SliceExists::as_exists_slice`].
+    #[inline]
+    pub(crate) fn as_exists_slice_mut(&mut self) -> &mut [Exists<T>] {
+        &mut self.0
+    }
+
+    /// Returns `true` if both slices have the same address and length.
+    ///
+    /// This compares the references themselves, not the values they point to.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.as_ptr() == other.as_ptr() && self.len() == other.len()
+    }
+
+    /// Returns `true` if the memory covered by the two slices has at least one byte in common.
+    ///
+    /// Empty slices and slices of zero-sized types never overlap anything.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1u32, 2, 3, 4];
+    /// let s = SliceExists::from_ref(&x);
+    /// assert!(s[..3].overlaps(&s[2..]));
+    /// assert!(!s[..2].overlaps(&s[2..]));
+    /// ```
+    #[inline]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        let (a, b) = (self.addr_range(), other.addr_range());
+        !a.is_empty() && !b.is_empty() && a.start < b.end && b.start < a.end
+    }
+
+    /// Returns the index of `elem` within this slice, or `None` if it does not
+    /// point to one of its elements.
+    ///
+    /// This is the same as [`Exists::offset_from_within`].
+    #[inline]
+    pub fn contains_exists(&self, elem: &Exists<T>) -> Option<usize> {
+        elem.offset_from_within(self)
+    }
+
+    /// Returns the range of indices that this slice covers within `parent`, or `None`
+    /// if it is not a subslice of `parent` starting on an element boundary.
+    ///
+    /// For a zero-sized `T`, every subslice shares an address, so this returns a range
+    /// starting at 0 if the addresses match and `self` is no longer than `parent`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1u32, 2, 3, 4];
+    /// let s = SliceExists::from_ref(&x);
+    /// assert_eq!(s[1..3].subslice_range(s), Some(1..3));
+    /// assert_eq!(s[4..].subslice_range(s), Some(4..4));
+    /// assert_eq!(s.subslice_range(&s[1..]), None);
+    /// ```
+    pub fn subslice_range(&self, parent: &Self) -> Option<ops::Range<usize>> {
+        let size = mem::size_of::<T>();
+        let byte_offset = (self.as_ptr() as usize).checked_sub(parent.as_ptr() as usize)?;
+        let start = if size == 0 {
+            (byte_offset == 0).then_some(0)?
+        } else {
+            (byte_offset % size == 0).then_some(byte_offset / size)?
+        };
+        let end = start.checked_add(self.len())?;
+        (end <= parent.len()).then_some(start..end)
+    }
+
+    /// Returns `true` if `next` begins exactly where this slice ends.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1u32, 2, 3, 4];
+    /// let s = SliceExists::from_ref(&x);
+    /// assert!(s[..1].is_adjacent(&s[1..]));
+    /// assert!(!s[1..].is_adjacent(&s[..1]));
+    /// ```
+    #[inline]
+    pub fn is_adjacent(&self, next: &Self) -> bool {
+        self.addr_range().end == next.as_ptr() as usize
+    }
+
+    /// Joins two adjacent slices into one, or returns `None` if `b` does not
+    /// begin exactly where `a` ends.
+    ///
+    /// # Safety
+    /// `a` and `b` must be derived from the same allocated object. Two separate
+    /// allocations may happen to be adjacent in memory, but a slice spanning both is
+    /// not valid to read through, since its address only grants access to `a`'s object.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1u32, 2, 3, 4];
+    /// let s = SliceExists::from_ref(&x);
+    /// let joined = unsafe { SliceExists::join_adjacent(&s[..1], &s[1..3]) }.unwrap();
+    /// assert!(joined.ptr_eq(&s[..3]));
+    /// assert!(unsafe { SliceExists::join_adjacent(&s[..1], &s[2..]) }.is_none());
+    /// ```
+    #[inline]
+    pub unsafe fn join_adjacent<'a>(a: &'a Self, b: &'a Self) -> Option<&'a Self> {
+        a.is_adjacent(b)
+            .then(|| Self::from_ptr(ptr::slice_from_raw_parts(a.as_ptr(), a.len() + b.len())))
+    }
+
+    /// Joins two adjacent mutable slices into one, or returns `None` if `b` does not
+    /// begin exactly where `a` ends.
+    ///
+    /// # Safety
+    /// `a` and `b` must be derived from the same allocated object,
+    /// as described in [`SliceExists::join_adjacent`].
+    #[inline]
+    pub unsafe fn join_adjacent_mut<'a>(a: &'a mut Self, b: &'a mut Self) -> Option<&'a mut Self> {
+        if a.is_adjacent(b) {
+            let len = a.len() + b.len();
+            Some(Self::from_mut_ptr(ptr::slice_from_raw_parts_mut(
+                a.as_mut_ptr(),
+                len,
+            )))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the byte addresses covered by this slice.
+    #[inline]
+    fn addr_range(&self) -> ops::Range<usize> {
+        let start = self.as_ptr() as usize;
+        start..start + self.len() * mem::size_of::<T>()
+    }
+
     /// Returns a shared reference that this `Exists<T>` points to.
     ///
     /// # Safety
@@ -215,6 +437,24 @@ impl<T> SliceExists<T> {
         &*self.as_raw_slice()
     }
 
+    /// Returns a unique mutable reference to the slice that this `SliceExists<T>` points to.
+    ///
+    /// # Safety
+    /// For the duration of the returned lifetime, the slice must be:
+    /// - [Valid][valid] for both reads and writes for `len * mem::size_of::<T>()` many bytes.
+    /// - Pointing to `len` contiguous properly initialized values of type `T`.
+    /// - Not read or written to via any methods other than the return value.
+    ///   This *includes* reading or writing via another `SliceExists<T>` or `Exists<T>`.
+    /// - Not aliasing a `&[T]`, `&UnsafeCell<[T]>` or `&mut [T]`
+    ///
+    /// You must enforce Rust's aliasing rules regarding `&mut [T]`.
+    /// This applies even if the result is unused.
+    ///
+    /// [valid]: https://doc.rust-lang.org/std/ptr/index.html#safety
+    pub unsafe fn as_mut_unchecked(&mut self) -> &mut [T] {
+        &mut *ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), self.len())
+    }
+
     /// Returns an existential reference to an element or subslice depending on the type of index.
     ///
     /// - If given a position, returns a `&Exists<T>` at that position or `None` if out of bounds.
@@ -244,6 +484,112 @@ impl<T> SliceExists<T> {
         index.get_mut(self)
     }
 
+    /// Returns an existential reference to an element or subslice depending on the type of index
+    /// (see [`get`]), or an [`IndexError`] describing why the index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// # use exists_ref::slice::IndexErrorKind;
+    /// let s = SliceExists::from_ref(&[1, 2, 3]);
+    /// assert_eq!(s.try_get(2).unwrap().get(), 3);
+    /// assert_eq!(s.try_get(3).err().unwrap().kind(), IndexErrorKind::OutOfBounds);
+    /// assert_eq!(
+    ///     s.try_get(2..1).err().unwrap().kind(),
+    ///     IndexErrorKind::StartAfterEnd { end: 1 },
+    /// );
+    /// assert_eq!(s.try_get(..=usize::MAX).err().unwrap().kind(), IndexErrorKind::EndOverflow);
+    /// ```
+    ///
+    /// [`get`]: SliceExists::get
+    #[inline]
+    pub fn try_get<I>(&self, index: I) -> Result<&I::Output, IndexError>
+    where
+        I: SliceExistsIndex<SliceExists<T>>,
+    {
+        index.try_index(self)
+    }
+
+    /// Returns a mutable existential reference to an element or subslice depending on the type
+    /// of index (see [`get`]), or an [`IndexError`] describing why the index is out of bounds.
+    ///
+    /// [`get`]: SliceExists::get
+    #[inline]
+    pub fn try_get_mut<I>(&mut self, index: I) -> Result<&mut I::Output, IndexError>
+    where
+        I: SliceExistsIndex<SliceExists<T>>,
+    {
+        index.try_index_mut(self)
+    }
+
+    /// Returns existential references to several elements or subslices at once,
+    /// or `None` if any index is out of bounds.
+    ///
+    /// The indices may be any one of the types accepted by [`get`], and may repeat or overlap.
+    ///
+    /// Like [`get`], this returns shared references. Mutable references, including
+    /// `[&mut Exists<T>; N]` for an array of `usize` indices, come from [`get_many_mut`].
+    ///
+    /// [`get`]: SliceExists::get
+    /// [`get_many_mut`]: SliceExists::get_many_mut
+    #[inline]
+    pub fn get_many<I, const N: usize>(&self, indices: [I; N]) -> Option<[&I::Output; N]>
+    where
+        I: SliceExistsIndex<SliceExists<T>>,
+    {
+        let mut indices = indices.into_iter();
+        let out = [self; N].map(|s| indices.next().and_then(|i| i.get(s)));
+        out.iter()
+            .all(Option::is_some)
+            .then(|| out.map(Option::unwrap))
+    }
+
+    /// Returns mutable existential references to several elements or subslices at once,
+    /// or `None` if any index is out of bounds.
+    ///
+    /// Unlike `[T]::get_disjoint_mut`, the indices may repeat or overlap, since mutable
+    /// existential references may alias.
+    ///
+    /// This is the mutable counterpart of [`get_many`], following the `get`/`get_mut` naming
+    /// of the rest of the crate: `get_many_mut([usize; N])` returns
+    /// `Option<[&mut Exists<T>; N]>`, and an array of ranges returns
+    /// `Option<[&mut SliceExists<T>; N]>`.
+    ///
+    /// [`get_many`]: SliceExists::get_many
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4];
+    /// let s = SliceExists::from_mut(&mut x);
+    /// let [a, b, c] = s.get_many_mut([0, 3, 0]).unwrap();
+    /// a.swap(b);
+    /// c.set(c.get() * 10);
+    /// assert_eq!(x, [40, 2, 3, 1]);
+    ///
+    /// let s = SliceExists::from_mut(&mut x);
+    /// let [front, back] = s.get_many_mut([0..3, 1..4]).unwrap();
+    /// back.copy_from(front);
+    /// assert_eq!(x, [40, 40, 2, 3]);
+    /// assert!(SliceExists::from_mut(&mut x).get_many_mut([0, 4]).is_none());
+    /// ```
+    #[inline]
+    pub fn get_many_mut<I, const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> Option<[&mut I::Output; N]>
+    where
+        I: SliceExistsIndex<SliceExists<T>>,
+    {
+        let mut indices = indices.into_iter();
+        let out = self
+            .copy_mut::<N>()
+            .map(|s| indices.next().and_then(|i| i.get_mut(s)));
+        out.iter()
+            .all(Option::is_some)
+            .then(|| out.map(Option::unwrap))
+    }
+
     /// Returns an existential reference to an element or subslice, without doing bounds checking.
     ///
     /// For a safe alternative, see [`get`].
@@ -288,12 +634,201 @@ impl<T> SliceExists<T> {
         self.into_iter()
     }
 
+    /// Returns an iterator over `chunk_size` elements of the slice at a time, starting at the
+    /// beginning of the slice. The last chunk will be shorter if `chunk_size` does not divide
+    /// the length of the slice.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
     #[inline]
-    pub fn chunks(&self, chunk_size: usize) -> impl Iterator<Item = &SliceExists<T>> {
-        iter::Chunks {
-            v: self,
-            chunk_size,
-        }
+    #[track_caller]
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Chunks::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` mutable elements of the slice at a time,
+    /// starting at the beginning of the slice. See [`chunks`](SliceExists::chunks).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ChunksMut::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over exactly `chunk_size` elements of the slice at a time,
+    /// starting at the beginning of the slice. The trailing elements that do not fill a chunk
+    /// are available from [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn chunks_exact(&self, chunk_size: usize) -> ChunksExact<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ChunksExact::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over exactly `chunk_size` mutable elements of the slice at a time,
+    /// starting at the beginning of the slice. See [`chunks_exact`](SliceExists::chunks_exact).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> ChunksExactMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ChunksExactMut::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the slice at a time, starting at the
+    /// end of the slice. The last chunk will be shorter if `chunk_size` does not divide
+    /// the length of the slice.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn rchunks(&self, chunk_size: usize) -> RChunks<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        RChunks::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` mutable elements of the slice at a time,
+    /// starting at the end of the slice. See [`rchunks`](SliceExists::rchunks).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn rchunks_mut(&mut self, chunk_size: usize) -> RChunksMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        RChunksMut::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over exactly `chunk_size` elements of the slice at a time,
+    /// starting at the end of the slice. The leading elements that do not fill a chunk
+    /// are available from [`RChunksExact::remainder`].
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn rchunks_exact(&self, chunk_size: usize) -> RChunksExact<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        RChunksExact::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over exactly `chunk_size` mutable elements of the slice at a time,
+    /// starting at the end of the slice. See [`rchunks_exact`](SliceExists::rchunks_exact).
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn rchunks_exact_mut(&mut self, chunk_size: usize) -> RChunksExactMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        RChunksExactMut::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over all contiguous windows of length `size`.
+    /// The windows overlap. If the slice is shorter than `size`, nothing is yielded.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn windows(&self, size: usize) -> Windows<'_, T> {
+        assert!(size != 0, "window size must be non-zero");
+        Windows::new(self, size)
+    }
+
+    /// Returns an iterator over all contiguous mutable windows of length `size`.
+    ///
+    /// Unlike `[T]`, this is possible because existential references may alias:
+    /// every window yielded may be kept and written to, even though they overlap.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4];
+    /// for w in SliceExists::from_mut(&mut x).windows_mut(2) {
+    ///     let sum = w[0].get() + w[1].get();
+    ///     w[1].set(sum);
+    /// }
+    /// assert_eq!(x, [1, 3, 6, 10]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn windows_mut(&mut self, size: usize) -> WindowsMut<'_, T> {
+        assert!(size != 0, "window size must be non-zero");
+        WindowsMut::new(self, size)
+    }
+
+    /// Returns an iterator over runs of elements, separating them where `pred`
+    /// returns `false` for two adjacent elements.
+    #[inline]
+    pub fn chunk_by<F>(&self, pred: F) -> ChunkBy<'_, T, F>
+    where
+        F: FnMut(&Exists<T>, &Exists<T>) -> bool,
+    {
+        ChunkBy::new(self, pred)
+    }
+
+    /// Returns an iterator over mutable runs of elements, separating them where `pred`
+    /// returns `false` for two adjacent elements.
+    #[inline]
+    pub fn chunk_by_mut<F>(&mut self, pred: F) -> ChunkByMut<'_, T, F>
+    where
+        F: FnMut(&Exists<T>, &Exists<T>) -> bool,
+    {
+        ChunkByMut::new(self, pred)
+    }
+
+    /// Returns an iterator over subslices separated by elements that match `pred`.
+    /// The matched element is not contained in the subslices.
+    #[inline]
+    pub fn split<F>(&self, pred: F) -> Split<'_, T, F>
+    where
+        F: FnMut(&Exists<T>) -> bool,
+    {
+        Split::new(self, pred)
+    }
+
+    /// Returns an iterator over mutable subslices separated by elements that match `pred`.
+    /// The matched element is not contained in the subslices.
+    #[inline]
+    pub fn split_mut<F>(&mut self, pred: F) -> SplitMut<'_, T, F>
+    where
+        F: FnMut(&Exists<T>) -> bool,
+    {
+        SplitMut::new(self, pred)
+    }
+
+    /// Returns an iterator over at most `n` subslices separated by elements that match `pred`.
+    /// The last subslice returned contains the remainder of the slice.
+    #[inline]
+    pub fn splitn<F>(&self, n: usize, pred: F) -> SplitN<'_, T, F>
+    where
+        F: FnMut(&Exists<T>) -> bool,
+    {
+        SplitN::new(self, n, pred)
+    }
+
+    /// Returns an iterator over subslices separated by elements that match `pred`,
+    /// starting at the end of the slice and working backwards.
+    #[inline]
+    pub fn rsplit<F>(&self, pred: F) -> RSplit<'_, T, F>
+    where
+        F: FnMut(&Exists<T>) -> bool,
+    {
+        RSplit::new(self, pred)
     }
 
     #[inline]
@@ -301,71 +836,1219 @@ impl<T> SliceExists<T> {
         self.len() == 0
     }
 
-    pub fn split_at(&self, index: usize) -> (&Self, &Self) {
-        // todo: maybe optimize?
-        (&self[..index], &self[index..])
+    /// Returns the first element of the slice, or `None` if it is empty.
+    #[inline]
+    pub fn first(&self) -> Option<&Exists<T>> {
+        self.get(0)
     }
-}
 
-impl<'a, T: 'a> From<&'a [T]> for &'a SliceExists<T> {
-    /// Constructs an existential slice reference from a shared reference.
+    /// Returns a mutable reference to the first element of the slice, or `None` if it is empty.
     #[inline]
-    fn from(item: &'a [T]) -> &'a SliceExists<T> {
-        // Safety: the raw pointer is derived from a valid reference.
-        unsafe { SliceExists::from_ptr(item) }
+    pub fn first_mut(&mut self) -> Option<&mut Exists<T>> {
+        self.get_mut(0)
     }
-}
 
-impl<'a, T: 'a> From<&'a Cell<[T]>> for &'a mut SliceExists<T> {
-    /// Constructs an existential slice reference from a shared mutable reference.
+    /// Returns the last element of the slice, or `None` if it is empty.
     #[inline]
-    fn from(item: &'a Cell<[T]>) -> &'a mut SliceExists<T> {
-        item.as_slice_of_cells().into()
+    pub fn last(&self) -> Option<&Exists<T>> {
+        self.get(self.len().checked_sub(1)?)
     }
-}
 
-impl<'a, T: 'a> From<&'a [Cell<T>]> for &'a mut SliceExists<T> {
-    /// Constructs an existential slice reference from a shared reference of cells.
+    /// Returns a mutable reference to the last element of the slice, or `None` if it is empty.
     #[inline]
-    fn from(item: &'a [Cell<T>]) -> &'a mut SliceExists<T> {
-        // Safety: the raw pointer is derived from a valid shared reference to mutable data.
-        unsafe { SliceExists::from_mut_ptr(item as *const [Cell<T>] as *const [T] as *mut [T]) }
+    pub fn last_mut(&mut self) -> Option<&mut Exists<T>> {
+        self.get_mut(self.len().checked_sub(1)?)
     }
-}
 
-impl<'a, T: 'a> From<&'a mut [T]> for &'a mut SliceExists<T> {
-    /// Constructs an existential slice reference from a unique mutable reference.
+    /// Returns the first element and the rest of the slice, or `None` if it is empty.
     #[inline]
-    fn from(item: &'a mut [T]) -> &'a mut SliceExists<T> {
-        // Safety: the raw pointer is derived from a valid mut reference.
-        unsafe { SliceExists::from_mut_ptr(item) }
+    pub fn split_first(&self) -> Option<(&Exists<T>, &Self)> {
+        let (first, rest) = self.split_at_checked(1)?;
+        Some((&first[0], rest))
     }
-}
 
-impl<T, I> Index<I> for SliceExists<T>
-where
-    I: SliceExistsIndex<SliceExists<T>>,
-{
-    type Output = <I as SliceExistsIndex<SliceExists<T>>>::Output;
+    /// Returns the first element and the rest of the slice mutably, or `None` if it is empty.
+    #[inline]
+    pub fn split_first_mut(&mut self) -> Option<(&mut Exists<T>, &mut Self)> {
+        let (first, rest) = self.split_at_mut_checked(1)?;
+        Some((&mut first[0], rest))
+    }
 
+    /// Returns the last element and the rest of the slice, or `None` if it is empty.
     #[inline]
-    fn index(&self, index: I) -> &Self::Output {
-        index.index(self)
+    pub fn split_last(&self) -> Option<(&Exists<T>, &Self)> {
+        let (rest, last) = self.split_at_checked(self.len().checked_sub(1)?)?;
+        Some((&last[0], rest))
     }
-}
 
-impl<T, I> IndexMut<I> for SliceExists<T>
-where
-    I: SliceExistsIndex<SliceExists<T>>,
-{
+    /// Returns the last element and the rest of the slice mutably, or `None` if it is empty.
     #[inline]
-    fn index_mut(&mut self, index: I) -> &mut Self::Output {
-        index.index_mut(self)
+    pub fn split_last_mut(&mut self) -> Option<(&mut Exists<T>, &mut Self)> {
+        let (rest, last) = self.split_at_mut_checked(self.len().checked_sub(1)?)?;
+        Some((&mut last[0], rest))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    // #[test]
-    // fn
+    /// Divides the slice into two at an index.
+    ///
+    /// The first will contain all indices from `[0, mid)` and the second will
+    /// contain all indices from `[mid, len)`.
+    ///
+    /// # Panics
+    /// Panics if `mid > len`.
+    #[inline]
+    #[track_caller]
+    pub fn split_at(&self, mid: usize) -> (&Self, &Self) {
+        match self.split_at_checked(mid) {
+            Some(pair) => pair,
+            None => panic!("mid > len"),
+        }
+    }
+
+    /// Divides the mutable slice into two at an index.
+    ///
+    /// The first will contain all indices from `[0, mid)` and the second will
+    /// contain all indices from `[mid, len)`.
+    ///
+    /// # Panics
+    /// Panics if `mid > len`.
+    #[inline]
+    #[track_caller]
+    pub fn split_at_mut(&mut self, mid: usize) -> (&mut Self, &mut Self) {
+        match self.split_at_mut_checked(mid) {
+            Some(pair) => pair,
+            None => panic!("mid > len"),
+        }
+    }
+
+    /// Divides the slice into two at an index, returning `None` if `mid > len`.
+    #[inline]
+    pub fn split_at_checked(&self, mid: usize) -> Option<(&Self, &Self)> {
+        if mid <= self.len() {
+            // Safety: `mid` was just checked.
+            Some(unsafe { self.split_at_unchecked(mid) })
+        } else {
+            None
+        }
+    }
+
+    /// Divides the mutable slice into two at an index, returning `None` if `mid > len`.
+    #[inline]
+    pub fn split_at_mut_checked(&mut self, mid: usize) -> Option<(&mut Self, &mut Self)> {
+        if mid <= self.len() {
+            // Safety: `mid` was just checked.
+            Some(unsafe { self.split_at_mut_unchecked(mid) })
+        } else {
+            None
+        }
+    }
+
+    /// Divides the slice into two at an index, without doing bounds checking.
+    ///
+    /// For a safe alternative, see [`split_at`].
+    ///
+    /// # Safety
+    /// Calling this method with `mid > len` is undefined behavior even if the result is unused.
+    ///
+    /// [`split_at`]: SliceExists::split_at
+    #[inline]
+    pub unsafe fn split_at_unchecked(&self, mid: usize) -> (&Self, &Self) {
+        // Both halves are built from `T` pointers, since subslicing `[Exists<T>]` would
+        // address elements with a zero stride.
+        let p = self.as_ptr();
+        (
+            Self::from_ptr(ptr::slice_from_raw_parts(p, mid)),
+            Self::from_ptr(ptr::slice_from_raw_parts(p.add(mid), self.len() - mid)),
+        )
+    }
+
+    /// Divides the mutable slice into two at an index, without doing bounds checking.
+    ///
+    /// For a safe alternative, see [`split_at_mut`].
+    ///
+    /// # Safety
+    /// Calling this method with `mid > len` is undefined behavior even if the result is unused.
+    ///
+    /// [`split_at_mut`]: SliceExists::split_at_mut
+    #[inline]
+    pub unsafe fn split_at_mut_unchecked(&mut self, mid: usize) -> (&mut Self, &mut Self) {
+        let len = self.len();
+        let p = self.as_mut_ptr();
+        // See `split_at_unchecked`.
+        (
+            Self::from_mut_ptr(ptr::slice_from_raw_parts_mut(p, mid)),
+            Self::from_mut_ptr(ptr::slice_from_raw_parts_mut(p.add(mid), len - mid)),
+        )
+    }
+
+    /// Divides the mutable slice into two halves that share the region `[i, j)`.
+    ///
+    /// The first will contain all indices from `[0, j)` and the second will contain all
+    /// indices from `[i, len)`. Writes to the shared region through either half are
+    /// visible through the other, which is sound since existential references may alias.
+    ///
+    /// # Panics
+    /// Panics if `i > j` or `j > len`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4, 5];
+    /// let s = SliceExists::from_mut(&mut x);
+    /// let (head, tail) = s.overlapping_split_at(1, 3);
+    /// assert_eq!((head.len(), tail.len()), (3, 4));
+    /// head[2].set(30);
+    /// assert_eq!(tail[1].get(), 30);
+    /// tail.fill(0);
+    /// assert_eq!(x, [1, 0, 0, 0, 0]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn overlapping_split_at(&mut self, i: usize, j: usize) -> (&mut Self, &mut Self) {
+        assert!(i <= j, "overlap start {} > overlap end {}", i, j);
+        assert!(j <= self.len(), "overlap end {} > len {}", j, self.len());
+        let [a, b] = self.copy_mut();
+        // Safety: `i <= j <= len` was just checked.
+        unsafe { (a.get_unchecked_mut(..j), b.get_unchecked_mut(i..)) }
+    }
+
+    /// Removes the first element from a shared slice cursor and returns it,
+    /// or returns `None` if the slice is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [3u8, b'a', b'b', b'c', 9];
+    /// let mut input = SliceExists::from_ref(&x);
+    /// let len = input.split_off_first().unwrap().get() as usize;
+    /// let (word, rest) = input.split_at(len);
+    /// input = rest;
+    /// assert_eq!(word.values().collect::<Vec<_>>(), b"abc");
+    /// assert_eq!(input.len(), 1);
+    /// ```
+    #[inline]
+    pub fn split_off_first<'a>(self: &mut &'a Self) -> Option<&'a Exists<T>> {
+        let (first, rest) = self.split_first()?;
+        *self = rest;
+        Some(first)
+    }
+
+    /// Removes the last element from a shared slice cursor and returns it,
+    /// or returns `None` if the slice is empty.
+    #[inline]
+    pub fn split_off_last<'a>(self: &mut &'a Self) -> Option<&'a Exists<T>> {
+        let (last, rest) = self.split_last()?;
+        *self = rest;
+        Some(last)
+    }
+
+    /// Removes the first element from a mutable slice cursor and returns it,
+    /// or returns `None` if the slice is empty.
+    #[inline]
+    pub fn split_off_first_mut<'a>(self: &mut &'a mut Self) -> Option<&'a mut Exists<T>> {
+        // Check first, so an empty cursor keeps its address instead of `take`'s default.
+        if self.is_empty() {
+            return None;
+        }
+        let (first, rest) = mem::take(self).split_first_mut()?;
+        *self = rest;
+        Some(first)
+    }
+
+    /// Removes the last element from a mutable slice cursor and returns it,
+    /// or returns `None` if the slice is empty.
+    #[inline]
+    pub fn split_off_last_mut<'a>(self: &mut &'a mut Self) -> Option<&'a mut Exists<T>> {
+        // See `split_off_first_mut`.
+        if self.is_empty() {
+            return None;
+        }
+        let (last, rest) = mem::take(self).split_last_mut()?;
+        *self = rest;
+        Some(last)
+    }
+
+    /// Swaps two elements in the slice. If `a == b`, the slice is unchanged.
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` are out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let [x, y] = self.copy_mut();
+        x[a].swap(&mut y[b]);
+    }
+
+    /// Swaps all elements in `self` with those in `other`.
+    ///
+    /// The two slices may overlap. The result is the same as swapping `self[i]` with
+    /// `other[i]` one at a time in increasing order of `i`; non-overlapping slices are
+    /// swapped with a single [`ptr::swap_nonoverlapping`].
+    ///
+    /// # Panics
+    /// Panics if the two slices have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4];
+    /// let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+    /// a[..2].swap_with(&mut b[2..]);
+    /// assert_eq!(x, [3, 4, 1, 2]);
+    /// ```
+    #[track_caller]
+    pub fn swap_with(&mut self, other: &mut SliceExists<T>) {
+        let len = self.len();
+        if len != other.len() {
+            len_mismatch_fail(len, other.len());
+        }
+        let a = self.as_mut_ptr();
+        let b = other.as_mut_ptr();
+        let overlaps = (a as usize) < (b as usize).wrapping_add(len * mem::size_of::<T>())
+            && (b as usize) < (a as usize).wrapping_add(len * mem::size_of::<T>());
+        // Safety: both pointers are valid for reads and writes of `len` elements.
+        unsafe {
+            if overlaps {
+                for i in 0..len {
+                    ptr::swap(a.add(i), b.add(i));
+                }
+            } else {
+                ptr::swap_nonoverlapping(a, b, len);
+            }
+        }
+    }
+
+    /// Reverses the order of elements in the slice, in place.
+    pub fn reverse(&mut self) {
+        let len = self.len();
+        let p = self.as_mut_ptr();
+        for i in 0..len / 2 {
+            // Safety: `i` and `len - 1 - i` are distinct and in bounds.
+            unsafe { ptr::swap_nonoverlapping(p.add(i), p.add(len - 1 - i), 1) }
+        }
+    }
+
+    /// Rotates the slice in place such that the first `mid` elements move to the end.
+    ///
+    /// # Panics
+    /// Panics if `mid > len`.
+    #[track_caller]
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(mid <= self.len(), "mid > len");
+        let [a, b, c] = self.copy_mut();
+        a[..mid].reverse();
+        b[mid..].reverse();
+        c.reverse();
+    }
+
+    /// Rotates the slice in place such that the last `k` elements move to the front.
+    ///
+    /// # Panics
+    /// Panics if `k > len`.
+    #[track_caller]
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len(), "k > len");
+        self.rotate_left(self.len() - k);
+    }
+}
+
+impl<T: Copy> SliceExists<T> {
+    /// Returns an iterator that reads each element by value.
+    ///
+    /// This is equivalent to `self.iter().map(Exists::get)`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1, 2, 3];
+    /// let sum: i32 = SliceExists::from_ref(&x).values().sum();
+    /// assert_eq!(sum, 6);
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, T> {
+        Values::new(self)
+    }
+
+    /// Copies all elements of the slice into `dst`.
+    ///
+    /// This does not create an intermediate `&[T]`.
+    ///
+    /// # Panics
+    /// Panics if `dst` is not the same length as `self`.
+    #[inline]
+    #[track_caller]
+    pub fn copy_to_slice(&self, dst: &mut [T]) {
+        if self.len() != dst.len() {
+            len_mismatch_fail(dst.len(), self.len());
+        }
+        // Safety: both pointers are valid for `len` elements, and `ptr::copy`
+        // permits the source and destination to overlap.
+        unsafe { ptr::copy(self.as_ptr(), dst.as_mut_ptr(), self.len()) }
+    }
+
+    /// Fills the slice with `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        let p = self.as_mut_ptr();
+        for i in 0..self.len() {
+            // Safety: `i` is in bounds.
+            unsafe { p.add(i).write(value) }
+        }
+    }
+
+    /// Fills the slice with values returned by calling `f` repeatedly, in order.
+    #[inline]
+    pub fn fill_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        let p = self.as_mut_ptr();
+        for i in 0..self.len() {
+            // Safety: `i` is in bounds.
+            unsafe { p.add(i).write(f()) }
+        }
+    }
+
+    /// Copies all elements from `src` into `self`.
+    ///
+    /// `src` may overlap with `self`. This has memmove semantics: the result is as if
+    /// `src` were first copied to a temporary buffer and then into `self`.
+    ///
+    /// # Panics
+    /// Panics if the two slices have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4, 5];
+    /// let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+    /// a[1..].copy_from(&b[..4]);
+    /// assert_eq!(x, [1, 1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn copy_from(&mut self, src: &SliceExists<T>) {
+        if self.len() != src.len() {
+            len_mismatch_fail(self.len(), src.len());
+        }
+        // Safety: both pointers are valid for `len` elements, and `ptr::copy`
+        // permits the source and destination to overlap.
+        unsafe { ptr::copy(src.as_ptr(), self.as_mut_ptr(), self.len()) }
+    }
+
+    /// Copies the elements in `src` to the position starting at `dest`, within the slice.
+    ///
+    /// The ranges may overlap, with the same memmove semantics as [`copy_from`].
+    ///
+    /// # Panics
+    /// Panics if either range exceeds the end of the slice, or if the end of `src`
+    /// is before the start.
+    ///
+    /// [`copy_from`]: SliceExists::copy_from
+    #[track_caller]
+    pub fn copy_within<R>(&mut self, src: R, dest: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let ops::Range { start, end } = index::range(src, self.len());
+        let count = end - start;
+        assert!(dest <= self.len() - count, "dest is out of bounds");
+        let p = self.as_mut_ptr();
+        // Safety: both ranges were checked to be in bounds above.
+        unsafe { ptr::copy(p.add(start), p.add(dest), count) }
+    }
+
+    /// Returns `true` if the slice contains an element equal to `x`.
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.values().any(|v| v == *x)
+    }
+
+    /// Returns the index of the first element for which `pred` returns `true`.
+    #[inline]
+    pub fn position<F>(&self, mut pred: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.values().position(|v| pred(&v))
+    }
+
+    /// Returns the index of the last element for which `pred` returns `true`.
+    #[inline]
+    pub fn rposition<F>(&self, mut pred: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.values().rposition(|v| pred(&v))
+    }
+
+    /// Returns `true` if `needle` is a prefix of the slice.
+    ///
+    /// `needle` may be a `[T]`, `[T; N]`, or another `SliceExists<T>`, which may overlap `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1, 2, 3];
+    /// let s = SliceExists::from_ref(&x);
+    /// assert!(s.starts_with(&[1, 2]));
+    /// assert!(s.starts_with(&s[..1]));
+    /// assert!(!s.starts_with(&[2]));
+    /// ```
+    pub fn starts_with<S>(&self, needle: &S) -> bool
+    where
+        S: AsRef<SliceExists<T>> + ?Sized,
+        T: PartialEq,
+    {
+        let needle = needle.as_ref();
+        let n = needle.len();
+        n <= self.len() && self[..n].values().eq(needle.values())
+    }
+
+    /// Returns `true` if `needle` is a suffix of the slice.
+    ///
+    /// See [`starts_with`](SliceExists::starts_with).
+    pub fn ends_with<S>(&self, needle: &S) -> bool
+    where
+        S: AsRef<SliceExists<T>> + ?Sized,
+        T: PartialEq,
+    {
+        let needle = needle.as_ref();
+        let n = needle.len();
+        n <= self.len() && self[self.len() - n..].values().eq(needle.values())
+    }
+
+    /// Returns the subslice after `prefix`, or `None` if the slice does not start with it.
+    pub fn strip_prefix<S>(&self, prefix: &S) -> Option<&SliceExists<T>>
+    where
+        S: AsRef<SliceExists<T>> + ?Sized,
+        T: PartialEq,
+    {
+        let prefix = prefix.as_ref();
+        self.starts_with(prefix).then(|| &self[prefix.len()..])
+    }
+
+    /// Returns the subslice before `suffix`, or `None` if the slice does not end with it.
+    pub fn strip_suffix<S>(&self, suffix: &S) -> Option<&SliceExists<T>>
+    where
+        S: AsRef<SliceExists<T>> + ?Sized,
+        T: PartialEq,
+    {
+        let suffix = suffix.as_ref();
+        self.ends_with(suffix)
+            .then(|| &self[..self.len() - suffix.len()])
+    }
+
+    /// Binary searches this sorted slice for `x`.
+    ///
+    /// Returns `Ok` with the index of a matching element, or `Err` with the index where `x`
+    /// could be inserted while keeping the slice sorted.
+    /// See [`slice::binary_search`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search).
+    #[inline]
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|p| p.cmp(x))
+    }
+
+    /// Binary searches this sorted slice with a comparator function, which returns whether
+    /// the element it is passed is less than, equal to, or greater than the target.
+    ///
+    /// See [`binary_search`](SliceExists::binary_search).
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut size = self.len();
+        let mut left = 0;
+        let mut right = size;
+        while left < right {
+            let mid = left + size / 2;
+            // Safety: `mid < right <= len`.
+            match f(&unsafe { self.get_unchecked(mid) }.get()) {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+            size = right - left;
+        }
+        Err(left)
+    }
+
+    /// Binary searches this slice, sorted by the key extraction function `f`, for the key `b`.
+    ///
+    /// See [`binary_search`](SliceExists::binary_search).
+    #[inline]
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> B,
+        B: Ord,
+    {
+        self.binary_search_by(|k| f(k).cmp(b))
+    }
+
+    /// Returns the index of the first element for which `pred` returns `false`, assuming
+    /// the slice is partitioned so that all elements that match come first.
+    #[inline]
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.binary_search_by(|x| {
+            if pred(x) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_or_else(|i| i)
+    }
+
+    /// Sets each element of `self` to the element of `src` at the corresponding position
+    /// in `indices`, so that `self[i] = src[indices[i]]`.
+    ///
+    /// `self`, `src` and `indices` may all overlap. Elements are processed one at a time in
+    /// increasing order of `i`, and each step reads `indices[i]` and then `src[indices[i]]`
+    /// after the writes of the previous steps.
+    ///
+    /// # Panics
+    /// Panics if `indices` is not the same length as `self`, or if an index is out of bounds
+    /// for `src`. Elements before the failing one will have been written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let src = [10, 20, 30];
+    /// let mut dst = [0; 4];
+    /// SliceExists::from_mut(&mut dst)
+    ///     .gather(SliceExists::from_ref(&src), SliceExists::from_ref(&[2, 0, 0, 1]));
+    /// assert_eq!(dst, [30, 10, 10, 20]);
+    /// ```
+    #[track_caller]
+    pub fn gather(&mut self, src: &SliceExists<T>, indices: &SliceExists<usize>) {
+        if self.len() != indices.len() {
+            len_mismatch_fail(self.len(), indices.len());
+        }
+        for i in 0..self.len() {
+            let val = src[indices[i].get()].get();
+            self[i].set(val);
+        }
+    }
+
+    /// Writes each element of `self` to the position of `dst` given by the corresponding
+    /// element of `indices`, so that `dst[indices[i]] = self[i]`.
+    ///
+    /// `self`, `dst` and `indices` may all overlap. Elements are processed one at a time in
+    /// increasing order of `i`, and each step reads `indices[i]` and then `self[i]` after the
+    /// writes of the previous steps. If an index repeats, the last write to it wins.
+    ///
+    /// # Panics
+    /// Panics if `indices` is not the same length as `self`, or if an index is out of bounds
+    /// for `dst`. Elements before the failing one will have been written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [1, 2, 3, 4];
+    /// let [src, dst] = SliceExists::from_mut(&mut x).copy_mut();
+    /// // Mirrors the front half onto the back half, through aliasing references.
+    /// src[..2].scatter(dst, SliceExists::from_ref(&[3, 2]));
+    /// assert_eq!(x, [1, 2, 2, 1]);
+    /// ```
+    #[track_caller]
+    pub fn scatter(&self, dst: &mut SliceExists<T>, indices: &SliceExists<usize>) {
+        if self.len() != indices.len() {
+            len_mismatch_fail(indices.len(), self.len());
+        }
+        for i in 0..self.len() {
+            let index = indices[i].get();
+            dst[index].set(self[i].get());
+        }
+    }
+
+    /// Writes elements from `iter` into the slice, starting at the beginning, until
+    /// either the slice is full or the iterator is exhausted.
+    ///
+    /// Returns the number of elements written. Elements past that count are untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [0; 4];
+    /// let written = SliceExists::from_mut(&mut x).write_from_iter(1..=3);
+    /// assert_eq!(written, 3);
+    /// assert_eq!(x, [1, 2, 3, 0]);
+    /// ```
+    pub fn write_from_iter<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let len = self.len();
+        let dst = self.as_mut_ptr();
+        let mut written = 0;
+        for val in iter.into_iter().take(len) {
+            // Safety: `written < len`, so the write is in bounds.
+            unsafe { dst.add(written).write(val) };
+            written += 1;
+        }
+        written
+    }
+
+    /// Copies the elements of the slice into a new `Vec`.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T> {
+        let len = self.len();
+        let mut v = Vec::with_capacity(len);
+        // Safety: the source is valid for `len` reads, and the fresh allocation has
+        // capacity for `len` elements and cannot overlap with it.
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr(), v.as_mut_ptr(), len);
+            v.set_len(len);
+        }
+        v
+    }
+
+    /// Copies the elements of the slice into a new boxed slice.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_boxed_slice(&self) -> Box<[T]> {
+        self.to_vec().into_boxed_slice()
+    }
+}
+
+/// Sorting and selection.
+///
+/// These only ever read and write elements through raw pointers, so they may be used on slices
+/// that other existential references point into. Comparators and key functions are passed
+/// references to copies of the elements, never references into the slice.
+impl<T: Copy> SliceExists<T> {
+    /// Sorts the slice, preserving the order of equal elements.
+    ///
+    /// Without the `alloc` feature, this is an in-place merge sort that does not allocate and
+    /// is `O(n log² n)` in the worst case. With it, the elements of all but short slices are
+    /// instead copied into a temporary buffer, sorted there in `O(n log n)`, and written back.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+    /// SliceExists::from_mut(&mut x).sort_by_key(|&(k, _)| k);
+    /// assert_eq!(x, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    /// ```
+    #[inline]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp)
+    }
+
+    /// Sorts the slice with a comparator function, preserving the order of equal elements.
+    ///
+    /// See [`sort`](SliceExists::sort).
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        #[cfg(feature = "alloc")]
+        if self.len() > sort::STABLE_IN_PLACE_MAX_LEN {
+            let mut buf = self.to_vec();
+            buf.sort_by(compare);
+            self.copy_from(SliceExists::from_ref(&buf));
+            return;
+        }
+        sort::sort_stable(self, |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    /// Sorts the slice with a key extraction function, preserving the order of equal elements.
+    ///
+    /// See [`sort`](SliceExists::sort).
+    #[inline]
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Sorts the slice in place, but might not preserve the order of equal elements.
+    ///
+    /// This does not allocate, and is `O(n log n)` in the worst case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [5, 4, 1, 3, 2];
+    /// let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+    /// a.sort_unstable();
+    /// assert!(b.is_sorted());
+    /// assert_eq!(x, [1, 2, 3, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        sort::sort_unstable(self, T::lt)
+    }
+
+    /// Sorts the slice in place with a comparator function, but might not preserve the order
+    /// of equal elements.
+    ///
+    /// See [`sort_unstable`](SliceExists::sort_unstable).
+    #[inline]
+    pub fn sort_unstable_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        sort::sort_unstable(self, |a, b| compare(a, b) == Ordering::Less)
+    }
+
+    /// Sorts the slice in place with a key extraction function, but might not preserve the
+    /// order of equal elements.
+    ///
+    /// See [`sort_unstable`](SliceExists::sort_unstable).
+    #[inline]
+    pub fn sort_unstable_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        sort::sort_unstable(self, |a, b| f(a).lt(&f(b)))
+    }
+
+    /// Reorders the slice such that the element at `index` is at its final sorted position.
+    ///
+    /// Returns the elements before `index`, none of which are greater than the element at
+    /// `index`, that element, and the elements after it, none of which are less than it.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
+    #[track_caller]
+    pub fn select_nth_unstable(
+        &mut self,
+        index: usize,
+    ) -> (&mut SliceExists<T>, &mut Exists<T>, &mut SliceExists<T>)
+    where
+        T: Ord,
+    {
+        if index >= self.len() {
+            panic!(
+                "partition_at_index index {} greater than length of slice {}",
+                index,
+                self.len()
+            );
+        }
+        sort::select_nth_unstable(self, index, T::lt);
+        let [a, b, c] = self.copy_mut();
+        (&mut a[..index], &mut b[index], &mut c[index + 1..])
+    }
+
+    /// Checks if the elements of this slice are sorted.
+    ///
+    /// Returns `false` if any two adjacent elements are not comparable.
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.windows(2).all(|w| w[0].get() <= w[1].get())
+    }
+}
+
+impl<T> AsRef<SliceExists<T>> for SliceExists<T> {
+    #[inline]
+    fn as_ref(&self) -> &SliceExists<T> {
+        self
+    }
+}
+
+impl<T> AsRef<SliceExists<T>> for [T] {
+    #[inline]
+    fn as_ref(&self) -> &SliceExists<T> {
+        self.into()
+    }
+}
+
+impl<T, const N: usize> AsRef<SliceExists<T>> for [T; N] {
+    #[inline]
+    fn as_ref(&self) -> &SliceExists<T> {
+        self[..].into()
+    }
+}
+
+impl<T> Default for &SliceExists<T> {
+    /// Creates an empty existential slice reference.
+    #[inline]
+    fn default() -> Self {
+        SliceExists::from_ref(&[])
+    }
+}
+
+impl<T> Default for &mut SliceExists<T> {
+    /// Creates an empty mutable existential slice reference.
+    #[inline]
+    fn default() -> Self {
+        SliceExists::from_mut(&mut [])
+    }
+}
+
+impl<'a, T: 'a> From<&'a [T]> for &'a SliceExists<T> {
+    /// Constructs an existential slice reference from a shared reference.
+    #[inline]
+    fn from(item: &'a [T]) -> &'a SliceExists<T> {
+        // Safety: the raw pointer is derived from a valid reference.
+        unsafe { SliceExists::from_ptr(item) }
+    }
+}
+
+impl<'a, T: 'a> From<&'a Cell<[T]>> for &'a mut SliceExists<T> {
+    /// Constructs an existential slice reference from a shared mutable reference.
+    #[inline]
+    fn from(item: &'a Cell<[T]>) -> &'a mut SliceExists<T> {
+        item.as_slice_of_cells().into()
+    }
+}
+
+impl<'a, T: 'a> From<&'a [Cell<T>]> for &'a mut SliceExists<T> {
+    /// Constructs an existential slice reference from a shared reference of cells.
+    #[inline]
+    fn from(item: &'a [Cell<T>]) -> &'a mut SliceExists<T> {
+        // Safety: the raw pointer is derived from a valid shared reference to mutable data.
+        unsafe { SliceExists::from_mut_ptr(item as *const [Cell<T>] as *const [T] as *mut [T]) }
+    }
+}
+
+impl<'a, T: 'a> From<&'a mut [T]> for &'a mut SliceExists<T> {
+    /// Constructs an existential slice reference from a unique mutable reference.
+    #[inline]
+    fn from(item: &'a mut [T]) -> &'a mut SliceExists<T> {
+        // Safety: the raw pointer is derived from a valid mut reference.
+        unsafe { SliceExists::from_mut_ptr(item) }
+    }
+}
+
+impl<T, I> Index<I> for SliceExists<T>
+where
+    I: SliceExistsIndex<SliceExists<T>>,
+{
+    type Output = <I as SliceExistsIndex<SliceExists<T>>>::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        index.index(self)
+    }
+}
+
+impl<T, I> IndexMut<I> for SliceExists<T>
+where
+    I: SliceExistsIndex<SliceExists<T>>,
+{
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        index.index_mut(self)
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn len_mismatch_fail(dst_len: usize, src_len: usize) -> ! {
+    panic!(
+        "source slice length ({}) does not match destination slice length ({})",
+        src_len, dst_len
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_to_slice() {
+        let x = [1, 2, 3];
+        let mut y = [0; 3];
+        SliceExists::from_ref(&x).copy_to_slice(&mut y);
+        assert_eq!(y, x);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match destination slice length")]
+    fn copy_to_slice_len_mismatch() {
+        let x = [1, 2, 3];
+        SliceExists::from_ref(&x).copy_to_slice(&mut [0; 2]);
+    }
+
+    #[test]
+    fn write_from_iter() {
+        let mut x = [0; 3];
+        let s = SliceExists::from_mut(&mut x);
+        assert_eq!(s.write_from_iter([7, 8, 9, 10]), 3);
+        assert_eq!(s.write_from_iter(core::iter::empty()), 0);
+        assert_eq!(x, [7, 8, 9]);
+    }
+
+    #[test]
+    fn fill() {
+        let mut x = [0; 4];
+        let s = SliceExists::from_mut(&mut x);
+        s[1..].fill(5);
+        assert_eq!(x, [0, 5, 5, 5]);
+        let mut n = 0;
+        SliceExists::from_mut(&mut x).fill_with(|| {
+            n += 1;
+            n
+        });
+        assert_eq!(x, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn copy_from_overlapping() {
+        let mut x = [1, 2, 3, 4, 5];
+        let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+        a[..4].copy_from(&b[1..]);
+        assert_eq!(x, [2, 3, 4, 5, 5]);
+    }
+
+    #[test]
+    fn copy_within() {
+        let mut x = [1, 2, 3, 4, 5];
+        let s = SliceExists::from_mut(&mut x);
+        s.copy_within(..3, 2);
+        assert_eq!(x, [1, 2, 1, 2, 3]);
+        let s = SliceExists::from_mut(&mut x);
+        s.copy_within(3..=4, 0);
+        assert_eq!(x, [2, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "dest is out of bounds")]
+    fn copy_within_out_of_bounds() {
+        let mut x = [1, 2, 3];
+        SliceExists::from_mut(&mut x).copy_within(1.., 2);
+    }
+
+    #[test]
+    fn swap() {
+        let mut x = [1, 2, 3];
+        let s = SliceExists::from_mut(&mut x);
+        s.swap(0, 2);
+        s.swap(1, 1);
+        assert_eq!(x, [3, 2, 1]);
+    }
+
+    #[test]
+    fn swap_with_overlapping() {
+        let mut x = [1, 2, 3, 4];
+        let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+        // Swaps (0, 1) then (1, 2) then (2, 3), bubbling the first element to the end.
+        a[..3].swap_with(&mut b[1..]);
+        assert_eq!(x, [2, 3, 4, 1]);
+    }
+
+    #[test]
+    fn reverse_and_rotate() {
+        let mut x = [1, 2, 3, 4, 5];
+        let s = SliceExists::from_mut(&mut x);
+        s.reverse();
+        assert_eq!(x, [5, 4, 3, 2, 1]);
+        let s = SliceExists::from_mut(&mut x);
+        s.rotate_left(2);
+        assert_eq!(x, [3, 2, 1, 5, 4]);
+        let s = SliceExists::from_mut(&mut x);
+        s.rotate_right(2);
+        assert_eq!(x, [5, 4, 3, 2, 1]);
+        let s = SliceExists::from_mut(&mut x);
+        s.rotate_left(5);
+        s.rotate_right(0);
+        assert_eq!(x, [5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn search() {
+        let x = [1, 3, 5, 7, 9];
+        let s = SliceExists::from_ref(&x);
+        assert!(s.contains(&5));
+        assert!(!s.contains(&4));
+        assert_eq!(s.position(|&v| v > 3), Some(2));
+        assert_eq!(s.rposition(|&v| v < 3), Some(0));
+        assert_eq!(s.binary_search(&7), Ok(3));
+        assert_eq!(s.binary_search(&0), Err(0));
+        assert_eq!(s.binary_search(&8), Err(4));
+        assert_eq!(s.binary_search(&10), Err(5));
+        assert_eq!(s.binary_search_by_key(&18, |&v| v * 2), Ok(4));
+        assert_eq!(s.partition_point(|&v| v < 6), 3);
+        assert_eq!(SliceExists::<i32>::from_ref(&[]).binary_search(&1), Err(0));
+    }
+
+    #[test]
+    fn prefix_suffix() {
+        let x = [1, 2, 3, 4];
+        let s = SliceExists::from_ref(&x);
+        assert!(s.starts_with(&[]));
+        assert!(s.ends_with(&x[2..]));
+        assert!(s.ends_with(&s[1..]));
+        assert!(!s.ends_with(&[1, 2, 3, 4, 5]));
+        assert_eq!(s.strip_prefix(&[1, 2]).map(|s| s.len()), Some(2));
+        assert_eq!(s.strip_suffix(&s[3..]).map(|s| s.len()), Some(3));
+        assert!(s.strip_prefix(&[2]).is_none());
+    }
+
+    #[test]
+    fn get_many() {
+        let x = [1, 2, 3];
+        let s = SliceExists::from_ref(&x);
+        let [a, b] = s.get_many([2, 2]).unwrap();
+        assert_eq!((a.get(), b.get()), (3, 3));
+        assert!(s.get_many([0, 3]).is_none());
+        assert!(s.get_many::<usize, 0>([]).is_some());
+        let [r, t] = s.get_many([0..2, 1..3]).unwrap();
+        assert_eq!((r.len(), t.len()), (2, 2));
+        assert!(s.get_many([0..=1, 1..=3]).is_none());
+    }
+
+    #[test]
+    fn split_at() {
+        let x = [1u16, 2, 3, 4];
+        let s = SliceExists::from_ref(&x);
+        for mid in 0..=4 {
+            let (a, b) = s.split_at(mid);
+            assert_eq!((a.len(), b.len()), (mid, 4 - mid));
+            assert_eq!(b.as_ptr(), x[mid..].as_ptr());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "mid > len")]
+    fn split_at_out_of_bounds() {
+        SliceExists::from_ref(&[1u8, 2]).split_at(3);
+    }
+
+    #[test]
+    fn first_last() {
+        let mut x = [1, 2, 3];
+        let s = SliceExists::from_mut(&mut x);
+        assert_eq!((s.first().unwrap().get(), s.last().unwrap().get()), (1, 3));
+        let (first, rest) = s.split_first_mut().unwrap();
+        first.set(10);
+        assert_eq!(rest.len(), 2);
+        let (last, rest) = s.split_last().unwrap();
+        assert_eq!((last.get(), rest.len()), (3, 2));
+        s.last_mut().unwrap().set(30);
+        assert_eq!(x, [10, 2, 30]);
+
+        let empty = <&SliceExists<u8>>::default();
+        assert!(empty.first().is_none() && empty.last().is_none());
+        assert!(empty.split_first().is_none() && empty.split_last().is_none());
+    }
+
+    #[test]
+    fn split_at_mut() {
+        let mut x = [1, 2, 3, 4];
+        let s = SliceExists::from_mut(&mut x);
+        assert!(s.split_at_mut_checked(5).is_none());
+        let (a, b) = s.split_at_mut(1);
+        a.fill(0);
+        b.fill(9);
+        assert_eq!(x, [0, 9, 9, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap end 4 > len 3")]
+    fn overlapping_split_at_out_of_bounds() {
+        SliceExists::from_mut(&mut [1, 2, 3]).overlapping_split_at(1, 4);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut x = [1, 2, 3];
+        let mut s = SliceExists::from_ref(&x);
+        assert_eq!(s.split_off_last().unwrap().get(), 3);
+        assert_eq!(s.split_off_first().unwrap().get(), 1);
+        assert_eq!(s.split_off_first().unwrap().get(), 2);
+        assert!(s.split_off_first().is_none() && s.split_off_last().is_none());
+
+        let mut s = SliceExists::from_mut(&mut x);
+        s.split_off_first_mut().unwrap().set(4);
+        s.split_off_last_mut().unwrap().set(6);
+        s[0].set(5);
+        let addr = s.as_ptr();
+        s.split_off_last_mut().unwrap();
+        // An empty cursor keeps its address.
+        assert!(s.split_off_first_mut().is_none() && s.split_off_last_mut().is_none());
+        assert_eq!((s.as_ptr(), s.len()), (addr, 0));
+        assert_eq!(x, [4, 5, 6]);
+    }
+
+    #[test]
+    fn address_queries() {
+        let mut x = [0u32; 6];
+        let [s, t] = SliceExists::from_mut(&mut x).copy_mut();
+        assert!(s[1..3].ptr_eq(&t[1..3]) && !s[1..3].ptr_eq(&t[1..4]));
+        assert!(s[1..3].overlaps(&s[2..]) && !s[1..3].overlaps(&s[3..]));
+        assert!(!s[2..2].overlaps(s));
+        assert_eq!(s[2..].contains_exists(&s[4]), Some(2));
+        assert_eq!(s[2..].contains_exists(&s[1]), None);
+        assert_eq!(s[2..5].subslice_range(&s[1..]), Some(1..4));
+        assert_eq!(s[2..].subslice_range(&s[..5]), None);
+
+        let (a, b) = (&mut s[..2], &mut t[2..5]);
+        assert!(a.is_adjacent(b) && !b.is_adjacent(a));
+        let joined = unsafe { SliceExists::join_adjacent_mut(a, b) }.unwrap();
+        joined.fill(7);
+        assert_eq!(x, [7, 7, 7, 7, 7, 0]);
+    }
+
+    #[test]
+    fn split_unchecked_addresses() {
+        let mut x = [1u32, 2, 3];
+        let s = SliceExists::from_mut(&mut x);
+        let addr = s.as_ptr();
+        assert_eq!(s.as_exists_slice().len(), 3);
+        let (head, tail) = unsafe { s.split_at_mut_unchecked(2) };
+        assert_eq!((head.as_ptr(), head.len()), (addr, 2));
+        assert_eq!((tail.as_ptr(), tail.len()), (addr.wrapping_add(2), 1));
+        head[1].set(7);
+        let (head, tail) = unsafe { s.split_at_unchecked(1) };
+        assert_eq!((head.as_ptr(), tail.as_ptr()), (addr, addr.wrapping_add(1)));
+        assert_eq!(x, [1, 7, 3]);
+    }
+
+    #[test]
+    fn gather_overlapping() {
+        let mut x = [0usize, 0, 1, 2];
+        let [dst, src, indices] = SliceExists::from_mut(&mut x).copy_mut();
+        // Each step sees the previous writes: x[0] = x[x[0]] = x[0], x[1] = x[x[1]] = x[0], ...
+        dst.gather(src, indices);
+        assert_eq!(x, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn scatter_duplicates() {
+        let mut dst = [0; 3];
+        SliceExists::from_ref(&[1, 2, 3]).scatter(
+            SliceExists::from_mut(&mut dst),
+            SliceExists::from_ref(&[1, 1, 0]),
+        );
+        assert_eq!(dst, [3, 2, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn gather_out_of_bounds() {
+        SliceExists::from_mut(&mut [0; 1])
+            .gather(SliceExists::from_ref(&[1, 2]), SliceExists::from_ref(&[2]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn to_vec() {
+        let x = [Cell::new(1), Cell::new(2)];
+        let s = SliceExists::from_cell_slice(&x);
+        assert_eq!(s.to_vec(), [1, 2]);
+        assert_eq!(&*s.to_boxed_slice(), &[1, 2]);
+    }
 }
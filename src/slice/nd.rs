@@ -0,0 +1,748 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! N-dimensional views over existential slices.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut, RangeBounds};
+
+use super::index;
+use super::{Copied, SliceExists};
+use crate::Exists;
+
+/// A `D`-dimensional view of an existential slice.
+///
+/// Element `[i0, i1, ...]` is located `i0 * strides()[0] + i1 * strides()[1] + ...` bytes
+/// from element `[0, 0, ...]`. A stride may be negative, or zero to repeat the same
+/// element along an axis, as in a broadcast view.
+///
+/// Created by [`SliceExists::as_nd`].
+pub struct NdExists<'a, T, const D: usize> {
+    ptr: *const T,
+    shape: [usize; D],
+    strides: [isize; D],
+    _phantom: PhantomData<&'a SliceExists<T>>,
+}
+
+/// A mutable `D`-dimensional view of an existential slice.
+///
+/// Like `&mut SliceExists<T>`, the elements of the view may alias each other and
+/// other mutable views. In particular, writing through a broadcast view is sound,
+/// and writes to a repeated element are visible through all of its indices.
+///
+/// Created by [`SliceExists::as_nd_mut`].
+pub struct NdExistsMut<'a, T, const D: usize> {
+    v: NdExists<'a, T, D>,
+    _phantom: PhantomData<&'a mut SliceExists<T>>,
+}
+
+/// Returns the number of elements in a view of `shape`, or `None` on overflow.
+#[inline]
+fn checked_len(shape: &[usize]) -> Option<usize> {
+    shape.iter().try_fold(1usize, |n, &dim| n.checked_mul(dim))
+}
+
+/// Returns the byte strides of a contiguous row-major view of `shape`.
+#[inline]
+fn row_major_strides<T, const D: usize>(shape: [usize; D]) -> [isize; D] {
+    let mut strides = [0; D];
+    let mut stride = mem::size_of::<T>() as isize;
+    for axis in (0..D).rev() {
+        strides[axis] = stride;
+        // Only grows past the slice length once an axis is empty, in which case
+        // the strides are never used.
+        stride = stride.wrapping_mul(shape[axis] as isize);
+    }
+    strides
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn nd_fit_fail(shape: &[usize], len: usize) -> ! {
+    panic!(
+        "shape {:?} does not fit in a slice of length {}",
+        shape, len
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn nd_index_fail(index: &[usize], shape: &[usize]) -> ! {
+    panic!("index {:?} out of bounds for shape {:?}", index, shape)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn axis_fail(axis: usize, dims: usize) -> ! {
+    panic!("axis {} out of bounds for {} dimensions", axis, dims)
+}
+
+impl<'a, T, const D: usize> NdExists<'a, T, D> {
+    /// Constructs an N-dimensional view from its raw parts.
+    ///
+    /// # Safety
+    /// The number of elements in `shape` must not overflow a `usize`. For every index
+    /// within `shape`, the element at the sum of `index[k] * strides[k]` bytes from `data`
+    /// must meet the requirements of [`Exists::from_ptr`] for lifetime `'a`, and all of
+    /// these elements must be within a single allocated object.
+    #[inline]
+    pub unsafe fn from_raw_parts(data: *const T, shape: [usize; D], strides: [isize; D]) -> Self {
+        Self {
+            ptr: data,
+            shape,
+            strides,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the length of each axis.
+    #[inline]
+    pub fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// Returns the distance between consecutive elements along each axis, in bytes.
+    #[inline]
+    pub fn strides(&self) -> [isize; D] {
+        self.strides
+    }
+
+    /// Returns the number of elements in the view, counting repeated elements
+    /// of a broadcast view once per index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Returns `true` if any axis is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.shape.contains(&0)
+    }
+
+    /// Returns a raw pointer to the element at index `[0, 0, ...]`.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns an existential reference to the element at `index`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: [usize; D]) -> Option<&'a Exists<T>> {
+        let in_bounds = index.iter().zip(&self.shape).all(|(i, dim)| i < dim);
+        // Safety: `index` was just checked.
+        in_bounds.then(|| unsafe { self.get_unchecked(index) })
+    }
+
+    /// Returns an existential reference to the element at `index`, without doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the result is unused.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: [usize; D]) -> &'a Exists<T> {
+        let offset: isize = index
+            .iter()
+            .zip(&self.strides)
+            .map(|(&i, &stride)| i as isize * stride)
+            .sum();
+        Exists::from_ptr(self.ptr.byte_offset(offset))
+    }
+
+    /// Returns the view restricted to `range` along `axis`.
+    ///
+    /// # Panics
+    /// Panics if `axis >= D`, or if `range` is out of bounds for that axis,
+    /// with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn slice_axis<R: RangeBounds<usize>>(mut self, axis: usize, range: R) -> Self {
+        if axis >= D {
+            axis_fail(axis, D);
+        }
+        let range = index::range(range, self.shape[axis]);
+        // An empty view may start past the end of the buffer, so its address is
+        // computed with wrapping.
+        self.ptr = self
+            .ptr
+            .wrapping_byte_offset(range.start as isize * self.strides[axis]);
+        self.shape[axis] = range.len();
+        self
+    }
+
+    /// Returns the view with its axes reordered, so that axis `k` of the result
+    /// is axis `axes[k]` of this view.
+    ///
+    /// # Panics
+    /// Panics if `axes` is not a permutation of `0..D`.
+    #[inline]
+    #[track_caller]
+    pub fn permute_axes(self, axes: [usize; D]) -> Self {
+        let mut seen = [false; D];
+        for &axis in &axes {
+            if axis >= D || mem::replace(&mut seen[axis], true) {
+                panic!("{:?} is not a permutation of the axes", axes);
+            }
+        }
+        // Safety: the elements are the same as the old ones.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr,
+                axes.map(|axis| self.shape[axis]),
+                axes.map(|axis| self.strides[axis]),
+            )
+        }
+    }
+
+    /// Returns the view with axes `a` and `b` swapped.
+    ///
+    /// # Panics
+    /// Panics if `a >= D` or `b >= D`.
+    #[inline]
+    #[track_caller]
+    pub fn swap_axes(mut self, a: usize, b: usize) -> Self {
+        for axis in [a, b] {
+            if axis >= D {
+                axis_fail(axis, D);
+            }
+        }
+        self.shape.swap(a, b);
+        self.strides.swap(a, b);
+        self
+    }
+
+    /// Returns a view of this view repeated to fit `shape`, or `None` if the
+    /// shapes are incompatible.
+    ///
+    /// As in NumPy, the axes of this view are aligned with the last `D` axes of `shape`.
+    /// Each must either match the new length or have length 1, in which case its single
+    /// element is repeated with a stride of 0. The leading `E - D` axes are new, and
+    /// also have a stride of 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x = [1, 2, 3];
+    /// let row = SliceExists::from_ref(&x).as_nd([3]);
+    /// let grid = row.broadcast([2, 3]).unwrap();
+    /// assert_eq!(grid.values().collect::<Vec<_>>(), [1, 2, 3, 1, 2, 3]);
+    /// assert!(row.broadcast([2, 4]).is_none());
+    /// ```
+    pub fn broadcast<const E: usize>(&self, shape: [usize; E]) -> Option<NdExists<'a, T, E>> {
+        let new_axes = E.checked_sub(D)?;
+        checked_len(&shape)?;
+        let mut strides = [0; E];
+        for axis in 0..D {
+            let (dim, new_dim) = (self.shape[axis], shape[new_axes + axis]);
+            if dim == new_dim {
+                strides[new_axes + axis] = self.strides[axis];
+            } else if dim != 1 {
+                return None;
+            }
+        }
+        // Safety: every index within `shape` maps to an index within `self.shape`.
+        Some(unsafe { NdExists::from_raw_parts(self.ptr, shape, strides) })
+    }
+
+    /// Returns an iterator over the elements of the view, in logical row-major order.
+    #[inline]
+    pub fn iter(&self) -> NdIter<'a, T, D> {
+        NdIter {
+            v: *self,
+            index: [0; D],
+            remaining: self.len(),
+        }
+    }
+}
+
+impl<'a, T: Copy, const D: usize> NdExists<'a, T, D> {
+    /// Returns an iterator that reads each element of the view by value, in logical
+    /// row-major order.
+    #[inline]
+    pub fn values(&self) -> Copied<NdIter<'a, T, D>> {
+        Copied::new(self.iter())
+    }
+}
+
+impl<T, const D: usize> Clone for NdExists<'_, T, D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const D: usize> Copy for NdExists<'_, T, D> {}
+
+impl<T, const D: usize> Index<[usize; D]> for NdExists<'_, T, D> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: [usize; D]) -> &Exists<T> {
+        match self.get(index) {
+            Some(e) => e,
+            None => nd_index_fail(&index, &self.shape),
+        }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for NdExists<'a, T, D> {
+    type Item = &'a Exists<T>;
+    type IntoIter = NdIter<'a, T, D>;
+
+    #[inline]
+    fn into_iter(self) -> NdIter<'a, T, D> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const D: usize> NdExistsMut<'a, T, D> {
+    /// Constructs a mutable N-dimensional view from its raw parts.
+    ///
+    /// # Safety
+    /// As for [`NdExists::from_raw_parts`], except that every element must meet the
+    /// requirements of [`Exists::from_mut_ptr`] for lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_raw_parts(data: *mut T, shape: [usize; D], strides: [isize; D]) -> Self {
+        Self::from_nd(NdExists::from_raw_parts(data, shape, strides))
+    }
+
+    /// # Safety
+    /// `v` must have been derived from a mutable view, valid for writes for lifetime `'a`.
+    #[inline]
+    unsafe fn from_nd(v: NdExists<'a, T, D>) -> Self {
+        Self {
+            v,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the length of each axis.
+    #[inline]
+    pub fn shape(&self) -> [usize; D] {
+        self.v.shape
+    }
+
+    /// Returns the distance between consecutive elements along each axis, in bytes.
+    #[inline]
+    pub fn strides(&self) -> [isize; D] {
+        self.v.strides
+    }
+
+    /// Returns the number of elements in the view, counting repeated elements
+    /// of a broadcast view once per index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.v.len()
+    }
+
+    /// Returns `true` if any axis is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.v.is_empty()
+    }
+
+    /// Returns a raw mutable pointer to the element at index `[0, 0, ...]`.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.v.ptr as *mut T
+    }
+
+    /// Returns a shared view of the same elements.
+    #[inline]
+    pub fn as_nd(&self) -> NdExists<'_, T, D> {
+        self.v
+    }
+
+    /// Reborrows the view, so that it can be passed on without being consumed.
+    #[inline]
+    pub fn reborrow(&mut self) -> NdExistsMut<'_, T, D> {
+        // Safety: the copy is only usable for as long as `self` is borrowed.
+        unsafe { NdExistsMut::from_nd(self.v) }
+    }
+
+    /// Safely copies this mutable view into multiple identical views.
+    ///
+    /// Since this type does not assert aliasing of pointed memory, this can be done safely.
+    #[inline]
+    pub fn copy_mut<const N: usize>(&mut self) -> [NdExistsMut<'_, T, D>; N] {
+        // Safety: each copy is only usable for as long as `self` is borrowed.
+        [self.v; N].map(|v| unsafe { NdExistsMut::from_nd(v) })
+    }
+
+    /// Returns an existential reference to the element at `index`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: [usize; D]) -> Option<&Exists<T>> {
+        self.as_nd().get(index)
+    }
+
+    /// Returns a mutable existential reference to the element at `index`,
+    /// or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: [usize; D]) -> Option<&mut Exists<T>> {
+        let e = self.v.get(index)?;
+        // Safety: `self` is valid for writes.
+        Some(unsafe { e.assume_mut() })
+    }
+
+    /// Returns a mutable existential reference to the element at `index`,
+    /// without doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the result is unused.
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: [usize; D]) -> &mut Exists<T> {
+        self.v.get_unchecked(index).assume_mut()
+    }
+
+    /// Returns the view restricted to `range` along `axis`.
+    ///
+    /// See [`NdExists::slice_axis`].
+    #[inline]
+    #[track_caller]
+    pub fn slice_axis<R: RangeBounds<usize>>(self, axis: usize, range: R) -> Self {
+        // Safety: the new elements are a subset of the old ones.
+        unsafe { Self::from_nd(self.v.slice_axis(axis, range)) }
+    }
+
+    /// Returns the view with its axes reordered.
+    ///
+    /// See [`NdExists::permute_axes`].
+    #[inline]
+    #[track_caller]
+    pub fn permute_axes(self, axes: [usize; D]) -> Self {
+        // Safety: the elements are the same as the old ones.
+        unsafe { Self::from_nd(self.v.permute_axes(axes)) }
+    }
+
+    /// Returns the view with axes `a` and `b` swapped.
+    ///
+    /// See [`NdExists::swap_axes`].
+    #[inline]
+    #[track_caller]
+    pub fn swap_axes(self, a: usize, b: usize) -> Self {
+        // Safety: the elements are the same as the old ones.
+        unsafe { Self::from_nd(self.v.swap_axes(a, b)) }
+    }
+
+    /// Returns a mutable view of this view repeated to fit `shape`, or `None` if the
+    /// shapes are incompatible.
+    ///
+    /// See [`NdExists::broadcast`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// // Accumulate each row of a matrix into a single row, in place.
+    /// let mut sums = [0; 3];
+    /// let m = [1, 2, 3, 4, 5, 6];
+    /// let mut acc = SliceExists::from_mut(&mut sums).as_nd_mut([3]).broadcast([2, 3]).unwrap();
+    /// for (a, x) in acc.iter_mut().zip(SliceExists::from_ref(&m).iter()) {
+    ///     let sum = a.get() + x.get();
+    ///     a.set(sum);
+    /// }
+    /// assert_eq!(sums, [5, 7, 9]);
+    /// ```
+    #[inline]
+    pub fn broadcast<const E: usize>(self, shape: [usize; E]) -> Option<NdExistsMut<'a, T, E>> {
+        // Safety: the new elements are the same as the old ones, possibly repeated.
+        Some(unsafe { NdExistsMut::from_nd(self.v.broadcast(shape)?) })
+    }
+
+    /// Returns an iterator over the elements of the view, in logical row-major order.
+    #[inline]
+    pub fn iter(&self) -> NdIter<'_, T, D> {
+        self.as_nd().iter()
+    }
+
+    /// Returns an iterator over mutable existential references to the elements of the
+    /// view, in logical row-major order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> NdIterMut<'_, T, D> {
+        NdIterMut {
+            iter: self.v.iter(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, const D: usize> NdExistsMut<'_, T, D> {
+    /// Returns an iterator that reads each element of the view by value, in logical
+    /// row-major order.
+    #[inline]
+    pub fn values(&self) -> Copied<NdIter<'_, T, D>> {
+        Copied::new(self.iter())
+    }
+
+    /// Copies all elements from `src` into this view, in logical row-major order.
+    ///
+    /// The elements are copied one at a time, so `src` may overlap this view.
+    ///
+    /// # Panics
+    /// Panics if the two views have different shapes.
+    #[track_caller]
+    pub fn copy_from(&mut self, src: NdExists<'_, T, D>) {
+        assert!(
+            self.v.shape == src.shape,
+            "source shape {:?} does not match destination shape {:?}",
+            src.shape,
+            self.v.shape,
+        );
+        for (d, s) in self.iter_mut().zip(src.iter()) {
+            d.set(s.get());
+        }
+    }
+
+    /// Fills the view with `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        for e in self.iter_mut() {
+            e.set(value);
+        }
+    }
+}
+
+impl<T, const D: usize> Index<[usize; D]> for NdExistsMut<'_, T, D> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: [usize; D]) -> &Exists<T> {
+        &self.v[index]
+    }
+}
+
+impl<T, const D: usize> IndexMut<[usize; D]> for NdExistsMut<'_, T, D> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, index: [usize; D]) -> &mut Exists<T> {
+        // Safety: `self` is valid for writes.
+        unsafe { self.v[index].assume_mut() }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for NdExistsMut<'a, T, D> {
+    type Item = &'a mut Exists<T>;
+    type IntoIter = NdIterMut<'a, T, D>;
+
+    #[inline]
+    fn into_iter(self) -> NdIterMut<'a, T, D> {
+        NdIterMut {
+            iter: self.v.iter(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the elements of an N-dimensional view, in logical row-major order.
+///
+/// Created by [`NdExists::iter`].
+pub struct NdIter<'a, T, const D: usize> {
+    v: NdExists<'a, T, D>,
+    index: [usize; D],
+    remaining: usize,
+}
+
+impl<'a, T, const D: usize> Iterator for NdIter<'a, T, D> {
+    type Item = &'a Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a Exists<T>> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        // Safety: `index` is in bounds while elements remain.
+        let e = unsafe { self.v.get_unchecked(self.index) };
+        // Advance the index like an odometer, starting from the last axis.
+        for axis in (0..D).rev() {
+            self.index[axis] += 1;
+            if self.index[axis] < self.v.shape[axis] {
+                break;
+            }
+            self.index[axis] = 0;
+        }
+        Some(e)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for NdIter<'_, T, D> {}
+impl<T, const D: usize> FusedIterator for NdIter<'_, T, D> {}
+
+/// An iterator over mutable existential references to the elements of an
+/// N-dimensional view, in logical row-major order.
+///
+/// Created by [`NdExistsMut::iter_mut`].
+pub struct NdIterMut<'a, T, const D: usize> {
+    iter: NdIter<'a, T, D>,
+    _phantom: PhantomData<&'a mut SliceExists<T>>,
+}
+
+impl<'a, T, const D: usize> Iterator for NdIterMut<'a, T, D> {
+    type Item = &'a mut Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut Exists<T>> {
+        // Safety: the view this iterator was created from is valid for writes.
+        self.iter.next().map(|e| unsafe { e.assume_mut() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, const D: usize> ExactSizeIterator for NdIterMut<'_, T, D> {}
+impl<T, const D: usize> FusedIterator for NdIterMut<'_, T, D> {}
+
+impl<T> SliceExists<T> {
+    /// Views the start of the slice as a contiguous row-major array of `shape`.
+    ///
+    /// # Panics
+    /// Panics if the array does not fit in the slice.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let x: Vec<u32> = (0..24).collect();
+    /// let t = SliceExists::from_ref(&x).as_nd([2, 3, 4]);
+    /// assert_eq!(t[[1, 2, 3]].get(), 23);
+    /// let t = t.permute_axes([2, 0, 1]).slice_axis(0, 1..2);
+    /// assert_eq!(t.shape(), [1, 2, 3]);
+    /// assert_eq!(t[[0, 1, 2]].get(), 21);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn as_nd<const D: usize>(&self, shape: [usize; D]) -> NdExists<'_, T, D> {
+        if !matches!(checked_len(&shape), Some(n) if n <= self.len()) {
+            nd_fit_fail(&shape, self.len());
+        }
+        let strides = row_major_strides::<T, D>(shape);
+        // Safety: every element of the view was just checked to be an element of `self`.
+        unsafe { NdExists::from_raw_parts(self.as_ptr(), shape, strides) }
+    }
+
+    /// Views the start of the slice as a mutable contiguous row-major array of `shape`.
+    ///
+    /// # Panics
+    /// Panics if the array does not fit in the slice.
+    #[inline]
+    #[track_caller]
+    pub fn as_nd_mut<const D: usize>(&mut self, shape: [usize; D]) -> NdExistsMut<'_, T, D> {
+        let v = self.as_nd(shape);
+        // Safety: `self` is valid for writes.
+        unsafe { NdExistsMut::from_nd(v) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn row_major() {
+        let x: Vec<u32> = (0..24).collect();
+        let t = SliceExists::from_ref(&x).as_nd([2, 3, 4]);
+        assert_eq!(t.strides(), [48, 16, 4]);
+        assert!(t.values().eq(x.iter().copied()));
+        assert!(t.get([0, 3, 0]).is_none() && t.get([2, 0, 0]).is_none());
+        assert!(t
+            .slice_axis(1, 1..2)
+            .values()
+            .eq([4, 5, 6, 7, 16, 17, 18, 19]));
+        assert!(t.slice_axis(2, 4..).is_empty());
+        let empty = t.slice_axis(0, 1..1).permute_axes([2, 0, 1]);
+        assert_eq!((empty.shape(), empty.len()), ([4, 0, 3], 0));
+        assert!(empty.is_empty() && empty.iter().next().is_none());
+
+        let scalar = SliceExists::from_ref(&x).as_nd([]);
+        assert_eq!((scalar.len(), scalar[[]].get()), (1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "shape [2, 3] does not fit in a slice of length 5")]
+    fn does_not_fit() {
+        SliceExists::from_ref(&[0; 5]).as_nd([2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index [1, 3] out of bounds for shape [2, 3]")]
+    fn index_out_of_bounds() {
+        let _ = &SliceExists::from_ref(&[0; 6]).as_nd([2, 3])[[1, 3]];
+    }
+
+    #[test]
+    #[should_panic(expected = "[0, 0] is not a permutation of the axes")]
+    fn bad_permutation() {
+        SliceExists::from_ref(&[0; 6])
+            .as_nd([2, 3])
+            .permute_axes([0, 0]);
+    }
+
+    #[test]
+    fn permute() {
+        let x: Vec<u32> = (0..6).collect();
+        let t = SliceExists::from_ref(&x).as_nd([2, 3]);
+        assert!(t.swap_axes(0, 1).values().eq([0, 3, 1, 4, 2, 5]));
+        assert!(t.permute_axes([1, 0]).values().eq([0, 3, 1, 4, 2, 5]));
+    }
+
+    #[test]
+    fn broadcast() {
+        let x = [1, 2];
+        let col = SliceExists::from_ref(&x).as_nd([2, 1]);
+        let b = col.broadcast([2, 2, 3]).unwrap();
+        assert_eq!(b.strides(), [0, 4, 0]);
+        assert!(b.values().eq([1, 1, 1, 2, 2, 2, 1, 1, 1, 2, 2, 2]));
+        assert!(col.broadcast([3, 1]).is_none());
+        assert!(col.broadcast([1]).is_none());
+        assert!(col.broadcast([usize::MAX, 2, 2]).is_none());
+    }
+
+    #[test]
+    fn broadcast_writes() {
+        let mut x = [0u32; 3];
+        let mut b = SliceExists::from_mut(&mut x)
+            .as_nd_mut([3])
+            .broadcast([4, 3])
+            .unwrap();
+        for e in b.iter_mut() {
+            let n = e.get();
+            e.set(n + 1);
+        }
+        b[[3, 0]].set(10);
+        assert_eq!(b.get([0, 0]).unwrap().get(), 10);
+        assert_eq!(x, [10, 4, 4]);
+    }
+
+    #[test]
+    fn overlapping_copy() {
+        // Shift each row of a 2x3 array left by one column, in place.
+        let mut x = [1, 2, 3, 4, 5, 6];
+        let mut t = SliceExists::from_mut(&mut x).as_nd_mut([2, 3]);
+        let [dst, src] = t.copy_mut();
+        dst.slice_axis(1, ..2)
+            .copy_from(src.as_nd().slice_axis(1, 1..));
+        assert_eq!(x, [2, 3, 3, 5, 6, 6]);
+    }
+}
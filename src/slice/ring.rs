@@ -0,0 +1,527 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Circular views over existential slices.
+
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{self, Index, IndexMut, RangeBounds};
+
+use super::index::{self, IndexError, IndexErrorKind};
+use super::{Copied, SliceExists};
+use crate::Exists;
+
+/// A circular view of a mutable existential slice, starting at an offset.
+///
+/// Logical index `i` refers to element `(start + i) % len` of the underlying slice,
+/// so the view wraps around from the end of the slice to its beginning.
+///
+/// # Examples
+/// ```
+/// # use exists_ref::{slice::RingExists, SliceExists};
+/// let mut buf = [0; 5];
+/// let mut ring = RingExists::new(SliceExists::from_mut(&mut buf), 3);
+/// ring.copy_from(0, SliceExists::from_ref(&[1, 2, 3]));
+/// assert_eq!(buf, [3, 0, 0, 1, 2]);
+/// ```
+pub struct RingExists<'a, T> {
+    buf: &'a mut SliceExists<T>,
+    start: usize,
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn ring_len_fail(src_len: usize, len: usize) -> ! {
+    panic!(
+        "slice length ({}) is greater than ring length ({})",
+        src_len, len
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn empty_fail(index: usize) -> ! {
+    panic!("{}", IndexError::new(IndexErrorKind::OutOfBounds, index, 0))
+}
+
+impl<'a, T> RingExists<'a, T> {
+    /// Creates a circular view of `buf` whose logical index 0 is at `buf[start % len]`.
+    #[inline]
+    pub fn new(buf: &'a mut SliceExists<T>, start: usize) -> Self {
+        let start = start.checked_rem(buf.len()).unwrap_or(0);
+        Self { buf, start }
+    }
+
+    /// Returns the number of elements in the ring.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the index in the underlying slice of logical index 0.
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Moves logical index 0 forward by `n` elements, wrapping around.
+    ///
+    /// This does not move any elements.
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        self.start = self.wrap(n);
+    }
+
+    /// Returns the underlying slice.
+    #[inline]
+    pub fn as_slice(&self) -> &SliceExists<T> {
+        self.buf
+    }
+
+    /// Returns the underlying mutable slice.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut SliceExists<T> {
+        self.buf
+    }
+
+    /// Returns the underlying mutable slice, consuming the view.
+    #[inline]
+    pub fn into_slice(self) -> &'a mut SliceExists<T> {
+        self.buf
+    }
+
+    /// Returns the index in the underlying slice of logical index `i`, modulo the length.
+    #[inline]
+    fn wrap(&self, i: usize) -> usize {
+        let len = self.len();
+        match i.checked_rem(len) {
+            // Neither index exceeds `len`, so this cannot overflow.
+            Some(i) if i >= len - self.start => i - (len - self.start),
+            Some(i) => self.start + i,
+            None => 0,
+        }
+    }
+
+    /// Returns the ranges of the underlying slice that hold logical indices `[at, at + n)`,
+    /// in order. `n` must be at most the length.
+    #[inline]
+    fn segments(&self, at: usize, n: usize) -> (ops::Range<usize>, ops::Range<usize>) {
+        let start = self.wrap(at);
+        let first = n.min(self.len() - start);
+        (start..start + first, 0..n - first)
+    }
+
+    /// Returns an existential reference to the element at logical index `i`, modulo
+    /// the length, or `None` if the ring is empty.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&Exists<T>> {
+        let i = self.wrap(i);
+        self.buf.get(i)
+    }
+
+    /// Returns a mutable existential reference to the element at logical index `i`,
+    /// modulo the length, or `None` if the ring is empty.
+    #[inline]
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut Exists<T>> {
+        let i = self.wrap(i);
+        self.buf.get_mut(i)
+    }
+
+    /// Returns the ring's elements in logical order, as up to two contiguous slices.
+    ///
+    /// The second slice is empty if the ring does not wrap around.
+    #[inline]
+    pub fn as_slices(&self) -> (&SliceExists<T>, &SliceExists<T>) {
+        self.range(..)
+    }
+
+    /// Returns the ring's elements in logical order, as up to two contiguous mutable slices.
+    ///
+    /// The second slice is empty if the ring does not wrap around.
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut SliceExists<T>, &mut SliceExists<T>) {
+        self.range_mut(..)
+    }
+
+    /// Returns the elements in the logical `range`, as up to two contiguous slices.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::{slice::RingExists, SliceExists};
+    /// let mut buf = [0, 1, 2, 3, 4];
+    /// let ring = RingExists::new(SliceExists::from_mut(&mut buf), 3);
+    /// let (a, b) = ring.range(1..4);
+    /// assert_eq!(a.values().chain(b.values()).collect::<Vec<_>>(), [4, 0, 1]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn range<R: RangeBounds<usize>>(&self, range: R) -> (&SliceExists<T>, &SliceExists<T>) {
+        let range = index::range(range, self.len());
+        let (a, b) = self.segments(range.start, range.len());
+        (&self.buf[a], &self.buf[b])
+    }
+
+    /// Returns the elements in the logical `range`, as up to two contiguous mutable slices.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn range_mut<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> (&mut SliceExists<T>, &mut SliceExists<T>) {
+        let range = index::range(range, self.len());
+        let (a, b) = self.segments(range.start, range.len());
+        let [x, y] = self.buf.copy_mut();
+        (&mut x[a], &mut y[b])
+    }
+
+    /// Returns an iterator over the elements of the ring, in logical order.
+    #[inline]
+    pub fn iter(&self) -> RingIter<'_, T> {
+        let (a, b) = self.as_slices();
+        RingIter { a, b }
+    }
+
+    /// Returns an iterator over mutable existential references to the elements
+    /// of the ring, in logical order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> RingIterMut<'_, T> {
+        let (a, b) = self.as_mut_slices();
+        RingIterMut { a, b }
+    }
+}
+
+impl<T: Copy> RingExists<'_, T> {
+    /// Returns an iterator that reads each element by value, in logical order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::{slice::RingExists, SliceExists};
+    /// let mut buf = [0, 1, 2, 3, 4];
+    /// let ring = RingExists::new(SliceExists::from_mut(&mut buf), 3);
+    /// assert!(ring.values().eq([3, 4, 0, 1, 2]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Copied<RingIter<'_, T>> {
+        Copied::new(self.iter())
+    }
+
+    /// Copies all elements from `src` into the ring, starting at logical index `at`
+    /// modulo the length.
+    ///
+    /// This performs at most two copies with [`SliceExists::copy_from`], one for each
+    /// contiguous segment. Each is a memmove, but if `src` overlaps the ring, the second
+    /// copy reads any values written by the first.
+    ///
+    /// # Panics
+    /// Panics if `src` is longer than the ring.
+    #[track_caller]
+    pub fn copy_from(&mut self, at: usize, src: &SliceExists<T>) {
+        if src.len() > self.len() {
+            ring_len_fail(src.len(), self.len());
+        }
+        let (a, b) = self.segments(at, src.len());
+        let (src_a, src_b) = src.split_at(a.len());
+        let [x, y] = self.buf.copy_mut();
+        x[a].copy_from(src_a);
+        y[b].copy_from(src_b);
+    }
+
+    /// Copies elements from the ring into all of `dst`, starting at logical index `at`
+    /// modulo the length.
+    ///
+    /// This performs at most two copies with [`SliceExists::copy_from`], one for each
+    /// contiguous segment. Each is a memmove, but if `dst` overlaps the ring, the second
+    /// copy reads any values written by the first.
+    ///
+    /// # Panics
+    /// Panics if `dst` is longer than the ring.
+    #[track_caller]
+    pub fn copy_to(&self, at: usize, dst: &mut SliceExists<T>) {
+        if dst.len() > self.len() {
+            ring_len_fail(dst.len(), self.len());
+        }
+        let (a, b) = self.segments(at, dst.len());
+        let (dst_a, dst_b) = dst.split_at_mut(a.len());
+        dst_a.copy_from(&self.buf[a]);
+        dst_b.copy_from(&self.buf[b]);
+    }
+
+    /// Fills the ring with `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        self.buf.fill(value);
+    }
+}
+
+impl<T> Index<usize> for RingExists<'_, T> {
+    type Output = Exists<T>;
+
+    /// Returns the element at logical index `i`, modulo the length.
+    ///
+    /// # Panics
+    /// Panics if the ring is empty.
+    #[inline]
+    #[track_caller]
+    fn index(&self, i: usize) -> &Exists<T> {
+        match self.get(i) {
+            Some(e) => e,
+            None => empty_fail(i),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for RingExists<'_, T> {
+    /// Returns the element at logical index `i`, modulo the length.
+    ///
+    /// # Panics
+    /// Panics if the ring is empty.
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, i: usize) -> &mut Exists<T> {
+        match self.get_mut(i) {
+            Some(e) => e,
+            None => empty_fail(i),
+        }
+    }
+}
+
+/// An iterator over the elements of a ring, in logical order.
+///
+/// Created by [`RingExists::iter`].
+pub struct RingIter<'a, T> {
+    a: &'a SliceExists<T>,
+    b: &'a SliceExists<T>,
+}
+
+impl<'a, T> Iterator for RingIter<'a, T> {
+    type Item = &'a Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.a.is_empty() {
+            mem::swap(&mut self.a, &mut self.b);
+        }
+        let (first, rest) = self.a.split_first()?;
+        self.a = rest;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for RingIter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.b.is_empty() {
+            mem::swap(&mut self.a, &mut self.b);
+        }
+        let (last, rest) = self.b.split_last()?;
+        self.b = rest;
+        Some(last)
+    }
+}
+
+impl<T> Clone for RingIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            a: self.a,
+            b: self.b,
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for RingIter<'_, T> {}
+impl<T> FusedIterator for RingIter<'_, T> {}
+
+/// An iterator over mutable existential references to the elements of a ring, in
+/// logical order.
+///
+/// Created by [`RingExists::iter_mut`].
+pub struct RingIterMut<'a, T> {
+    a: &'a mut SliceExists<T>,
+    b: &'a mut SliceExists<T>,
+}
+
+impl<'a, T> Iterator for RingIterMut<'a, T> {
+    type Item = &'a mut Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.a.is_empty() {
+            mem::swap(&mut self.a, &mut self.b);
+        }
+        let (first, rest) = mem::take(&mut self.a).split_first_mut()?;
+        self.a = rest;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for RingIterMut<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.b.is_empty() {
+            mem::swap(&mut self.a, &mut self.b);
+        }
+        let (last, rest) = mem::take(&mut self.b).split_last_mut()?;
+        self.b = rest;
+        Some(last)
+    }
+}
+
+impl<T> ExactSizeIterator for RingIterMut<'_, T> {}
+impl<T> FusedIterator for RingIterMut<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraparound_indexing() {
+        let mut buf = [0, 1, 2, 3, 4];
+        let mut ring = RingExists::new(SliceExists::from_mut(&mut buf), 7);
+        assert_eq!(ring.start(), 2);
+        assert!(ring.values().eq([2, 3, 4, 0, 1]));
+        assert_eq!((ring[0].get(), ring[3].get(), ring[13].get()), (2, 0, 0));
+        assert_eq!(ring[usize::MAX].get(), buf_at(usize::MAX, 2, 5));
+        ring.advance(4);
+        assert!(ring.values().eq([1, 2, 3, 4, 0]));
+        ring[1].set(20);
+        assert_eq!(buf, [0, 1, 20, 3, 4]);
+    }
+
+    fn buf_at(i: usize, start: usize, len: usize) -> i32 {
+        ((i % len + start) % len) as i32
+    }
+
+    #[test]
+    fn slices_and_ranges() {
+        let mut buf = [0, 1, 2, 3, 4];
+        let mut ring = RingExists::new(SliceExists::from_mut(&mut buf), 3);
+        let (a, b) = ring.as_slices();
+        assert_eq!((a.len(), b.len()), (2, 3));
+        let (a, b) = ring.range(..2);
+        assert_eq!((a.len(), b.len()), (2, 0));
+        let (a, b) = ring.range(2..5);
+        assert_eq!((a.len(), b.len()), (3, 0));
+        let (a, b) = ring.range(5..);
+        assert!(a.is_empty() && b.is_empty());
+        let (a, b) = ring.range_mut(1..3);
+        a.fill(10);
+        b.fill(20);
+        assert_eq!(buf, [20, 1, 2, 3, 10]);
+
+        let mut buf = [0, 1, 2];
+        let ring = RingExists::new(SliceExists::from_mut(&mut buf), 0);
+        let (a, b) = ring.as_slices();
+        assert_eq!((a.len(), b.len()), (3, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 6 out of range for slice of length 5")]
+    fn range_out_of_bounds() {
+        let mut buf = [0; 5];
+        RingExists::new(SliceExists::from_mut(&mut buf), 1).range(2..6);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut buf = [0, 1, 2, 3, 4];
+        let mut ring = RingExists::new(SliceExists::from_mut(&mut buf), 3);
+        assert!(ring.values().rev().eq([2, 1, 0, 4, 3]));
+        let mut it = ring.iter();
+        assert_eq!(it.len(), 5);
+        assert_eq!(it.next_back().map(Exists::get), Some(2));
+        assert_eq!(it.next().map(Exists::get), Some(3));
+        assert!(it.map(Exists::get).eq([4, 0, 1]));
+        let mut it = ring.iter_mut();
+        it.next().unwrap().set(30);
+        it.next_back().unwrap().set(20);
+        for e in it.rev() {
+            e.set(e.get() * 10);
+        }
+        assert_eq!(buf, [0, 10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn copy() {
+        let mut buf = [0; 5];
+        let mut ring = RingExists::new(SliceExists::from_mut(&mut buf), 4);
+        ring.copy_from(6, SliceExists::from_ref(&[1, 2, 3, 4]));
+        let mut out = [0; 5];
+        ring.copy_to(0, SliceExists::from_mut(&mut out));
+        assert_eq!(out, [0, 1, 2, 3, 4]);
+        ring.copy_to(3, SliceExists::from_mut(&mut out[..3]));
+        assert_eq!(out[..3], [3, 4, 0]);
+        assert_eq!(buf, [1, 2, 3, 4, 0]);
+    }
+
+    #[test]
+    fn copy_overlapping() {
+        // Copy the ring onto itself, shifted by one.
+        let mut buf = [0, 1, 2, 3, 4];
+        let [a, b] = SliceExists::from_mut(&mut buf).copy_mut();
+        let mut ring = RingExists::new(a, 3);
+        ring.copy_from(1, &b[..4]);
+        assert_eq!(buf, [1, 2, 3, 3, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice length (4) is greater than ring length (3)")]
+    fn copy_too_long() {
+        let mut buf = [0; 3];
+        RingExists::new(SliceExists::from_mut(&mut buf), 0)
+            .copy_from(0, SliceExists::from_ref(&[0; 4]));
+    }
+
+    #[test]
+    fn empty() {
+        let mut ring = RingExists::new(<&mut SliceExists<u8>>::default(), 5);
+        assert_eq!(ring.start(), 0);
+        assert!(ring.get(3).is_none());
+        ring.advance(2);
+        ring.copy_from(1, <&SliceExists<u8>>::default());
+        assert_eq!(ring.iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 0 but the index is 3")]
+    fn index_empty() {
+        let ring = RingExists::new(<&mut SliceExists<u8>>::default(), 0);
+        let _ = &ring[3];
+    }
+}
@@ -0,0 +1,517 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sorting and selection on existential slices.
+//!
+//! The unstable sort is an introsort in the style of pdqsort: quicksort with a median-of-three
+//! (or ninther) pivot and a branchless Lomuto partition, insertion sort for short ranges, a
+//! heapsort fallback once the recursion gets too deep, and a separate pass that skips runs of
+//! elements equal to an ancestor pivot. The stable sort is a bottom-up merge sort over
+//! insertion-sorted runs, which merges in place by rotation so that it needs no buffer.
+//!
+//! Every element access is a raw pointer read or write of a `T: Copy`, so no `&T` or `&mut T`
+//! into the buffer is ever created; the comparator only ever sees references to copies held on
+//! the stack.
+
+use core::ptr;
+
+use crate::slice::SliceExists;
+
+/// Ranges at most this long are sorted with insertion sort.
+const INSERTION_SORT_THRESHOLD: usize = 20;
+
+/// Slices at most this long are stably sorted in place even when a buffer could be allocated.
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub(super) const STABLE_IN_PLACE_MAX_LEN: usize = INSERTION_SORT_THRESHOLD;
+
+/// Ranges at least this long use Tukey's ninther to choose a pivot.
+const NINTHER_THRESHOLD: usize = 50;
+
+/// Sorts `v` with `is_less`, without preserving the order of equal elements.
+pub(super) fn sort_unstable<T: Copy, F>(v: &mut SliceExists<T>, mut is_less: F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    if len < 2 {
+        return;
+    }
+
+    // Already sorted or strictly descending inputs are handled in a single pass.
+    let p = v.as_mut_ptr();
+    // Safety: `v` is valid for reads and writes of `len` elements.
+    unsafe {
+        let descending = is_less(&p.add(1).read(), &p.read());
+        let mut run = 2;
+        while run < len && descending == is_less(&p.add(run).read(), &p.add(run - 1).read()) {
+            run += 1;
+        }
+        if run == len {
+            if descending {
+                v.reverse();
+            }
+            return;
+        }
+    }
+
+    let limit = 2 * (usize::BITS - len.leading_zeros());
+    // Safety: `v` is valid for reads and writes of `len` elements.
+    unsafe { recurse(p, len, &mut is_less, None, limit) }
+}
+
+/// Sorts `v` with `is_less`, preserving the order of equal elements.
+///
+/// This does not allocate, and is `O(n log² n)` in the worst case.
+pub(super) fn sort_stable<T: Copy, F>(v: &mut SliceExists<T>, mut is_less: F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    let p = v.as_mut_ptr();
+    for start in (0..len).step_by(INSERTION_SORT_THRESHOLD) {
+        let run = core::cmp::min(INSERTION_SORT_THRESHOLD, len - start);
+        // Safety: `start..start + run` is within `v`, which is valid for reads and writes.
+        unsafe { insertion_sort(p.add(start), run, &mut is_less) }
+    }
+
+    let mut width = INSERTION_SORT_THRESHOLD;
+    while width < len {
+        for start in (0..len - width).step_by(2 * width) {
+            let end = core::cmp::min(start + 2 * width, len);
+            merge(&mut v[start..end], width, &mut is_less);
+        }
+        width *= 2;
+    }
+}
+
+/// Merges the sorted runs `v[..mid]` and `v[mid..]` in place.
+///
+/// This splits the longer run in half, finds where its middle element belongs in the other
+/// run, and rotates the two pieces between those points past each other, which leaves two
+/// smaller merges on either side.
+fn merge<T: Copy, F>(v: &mut SliceExists<T>, mid: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    if mid == 0 || mid == len {
+        return;
+    }
+    let p = v.as_mut_ptr();
+    // Safety: every index read is less than `len`.
+    let (left_cut, right_cut) = unsafe {
+        if !is_less(&p.add(mid).read(), &p.add(mid - 1).read()) {
+            return;
+        }
+        if len == 2 {
+            ptr::swap_nonoverlapping(p, p.add(1), 1);
+            return;
+        }
+        if mid >= len - mid {
+            // Equal elements of the right run go after those of the left run.
+            let left_cut = mid / 2;
+            let x = p.add(left_cut).read();
+            (
+                left_cut,
+                mid + partition_point(p.add(mid), len - mid, |e| is_less(e, &x)),
+            )
+        } else {
+            let right_cut = mid + (len - mid) / 2;
+            let x = p.add(right_cut).read();
+            (partition_point(p, mid, |e| !is_less(&x, e)), right_cut)
+        }
+    };
+    v[left_cut..right_cut].rotate_left(mid - left_cut);
+    let new_mid = left_cut + (right_cut - mid);
+    let [a, b] = v.copy_mut();
+    merge(&mut a[..new_mid], left_cut, is_less);
+    merge(&mut b[new_mid..], right_cut - new_mid, is_less);
+}
+
+/// Returns the number of leading elements among the `len` sorted elements at `p` for which
+/// `pred` returns `true`.
+unsafe fn partition_point<T: Copy, F>(p: *mut T, len: usize, mut pred: F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let m = lo + (hi - lo) / 2;
+        if pred(&p.add(m).read()) {
+            lo = m + 1;
+        } else {
+            hi = m;
+        }
+    }
+    lo
+}
+
+/// Reorders `v` so that the element at `index` is in its final sorted position.
+///
+/// The caller must check that `index < v.len()`.
+pub(super) fn select_nth_unstable<T: Copy, F>(v: &mut SliceExists<T>, index: usize, mut is_less: F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    debug_assert!(index < v.len());
+    let p = v.as_mut_ptr();
+    let mut lo = 0;
+    let mut hi = v.len();
+    let mut limit = 2 * (usize::BITS - hi.leading_zeros());
+    let mut ancestor = None;
+    // Safety: `lo..hi` always stays within `v`, which is valid for reads and writes.
+    unsafe {
+        loop {
+            let len = hi - lo;
+            if len <= INSERTION_SORT_THRESHOLD {
+                insertion_sort(p.add(lo), len, &mut is_less);
+                return;
+            }
+            if limit == 0 {
+                heapsort(p.add(lo), len, &mut is_less);
+                return;
+            }
+            limit -= 1;
+
+            let base = p.add(lo);
+            let pv = choose_pivot(base, len, &mut is_less);
+
+            // See `recurse`: elements equal to an ancestor pivot are already in place.
+            if let Some(a) = ancestor {
+                if !is_less(&a, &pv) {
+                    lo += partition(base.add(1), len - 1, |x| !is_less(&pv, x)) + 1;
+                    if index < lo {
+                        return;
+                    }
+                    ancestor = None;
+                    continue;
+                }
+            }
+
+            let mid = lo + partition(base.add(1), len - 1, |x| is_less(x, &pv));
+            ptr::swap(base, p.add(mid));
+            if !is_balanced(mid - lo, len) {
+                break_patterns(base, mid - lo);
+                break_patterns(p.add(mid + 1), hi - mid - 1);
+            }
+            match index.cmp(&mid) {
+                core::cmp::Ordering::Less => hi = mid,
+                core::cmp::Ordering::Equal => return,
+                core::cmp::Ordering::Greater => {
+                    lo = mid + 1;
+                    ancestor = Some(pv);
+                }
+            }
+        }
+    }
+}
+
+/// Sorts the `len` elements at `p`, recursing into the left side of each partition
+/// and looping on the right.
+///
+/// `ancestor` is a pivot that is no greater than every element at `p`, if there is one.
+unsafe fn recurse<T: Copy, F>(
+    mut p: *mut T,
+    mut len: usize,
+    is_less: &mut F,
+    mut ancestor: Option<T>,
+    mut limit: u32,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        if len <= INSERTION_SORT_THRESHOLD {
+            insertion_sort(p, len, is_less);
+            return;
+        }
+        if limit == 0 {
+            heapsort(p, len, is_less);
+            return;
+        }
+        limit -= 1;
+
+        let pv = choose_pivot(p, len, is_less);
+
+        // If the pivot equals the ancestor, every element no greater than it is equal to it
+        // and already in place, so only the elements greater than it are left to sort.
+        if let Some(a) = ancestor {
+            if !is_less(&a, &pv) {
+                let eq = partition(p.add(1), len - 1, |x| !is_less(&pv, x));
+                p = p.add(eq + 1);
+                len -= eq + 1;
+                ancestor = None;
+                continue;
+            }
+        }
+
+        let mid = partition(p.add(1), len - 1, |x| is_less(x, &pv));
+        ptr::swap(p, p.add(mid));
+        if !is_balanced(mid, len) {
+            break_patterns(p, mid);
+            break_patterns(p.add(mid + 1), len - mid - 1);
+        }
+        recurse(p, mid, is_less, ancestor, limit);
+        p = p.add(mid + 1);
+        len -= mid + 1;
+        ancestor = Some(pv);
+    }
+}
+
+/// Returns whether a partition of `len` elements that put `mid` elements on the left
+/// split them reasonably evenly.
+#[inline]
+fn is_balanced(mid: usize, len: usize) -> bool {
+    core::cmp::min(mid, len - mid) >= len / 8
+}
+
+/// Swaps a few elements near the middle of the `len` elements at `p` with pseudorandom
+/// others, so that patterns which fool the pivot choice do not keep doing so.
+unsafe fn break_patterns<T: Copy>(p: *mut T, len: usize) {
+    if len < 8 {
+        return;
+    }
+    let mut random = len as u32;
+    let modulus = len.next_power_of_two();
+    let pos = len / 4 * 2;
+    for i in 0..3 {
+        random ^= random << 13;
+        random ^= random >> 17;
+        random ^= random << 5;
+        let mut other = random as usize & (modulus - 1);
+        if other >= len {
+            other -= len;
+        }
+        ptr::swap(p.add(pos - 1 + i), p.add(other));
+    }
+}
+
+/// Sorts the `len` elements at `p` by inserting each one into the sorted prefix before it.
+unsafe fn insertion_sort<T: Copy, F>(p: *mut T, len: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in 1..len {
+        let x = p.add(i).read();
+        let mut j = i;
+        while j > 0 {
+            let prev = p.add(j - 1).read();
+            if !is_less(&x, &prev) {
+                break;
+            }
+            p.add(j).write(prev);
+            j -= 1;
+        }
+        p.add(j).write(x);
+    }
+}
+
+/// Sorts the `len` elements at `p` in `O(n log n)` worst case time.
+unsafe fn heapsort<T: Copy, F>(p: *mut T, len: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for node in (0..len / 2).rev() {
+        sift_down(p, len, node, is_less);
+    }
+    for end in (1..len).rev() {
+        ptr::swap_nonoverlapping(p, p.add(end), 1);
+        sift_down(p, end, 0, is_less);
+    }
+}
+
+/// Restores the max-heap property of the `len` elements at `p` below `node`.
+unsafe fn sift_down<T: Copy, F>(p: *mut T, len: usize, mut node: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        let mut child = 2 * node + 1;
+        if child >= len {
+            return;
+        }
+        if child + 1 < len && is_less(&p.add(child).read(), &p.add(child + 1).read()) {
+            child += 1;
+        }
+        if !is_less(&p.add(node).read(), &p.add(child).read()) {
+            return;
+        }
+        ptr::swap_nonoverlapping(p.add(node), p.add(child), 1);
+        node = child;
+    }
+}
+
+/// Returns the index of the median of the elements at `a`, `b` and `c`.
+unsafe fn median3<T: Copy, F>(p: *mut T, a: usize, b: usize, c: usize, is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let (x, y, z) = (p.add(a).read(), p.add(b).read(), p.add(c).read());
+    let ab = is_less(&x, &y);
+    if ab != is_less(&x, &z) {
+        a
+    } else if ab != is_less(&y, &z) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Chooses a pivot among the `len > 2` elements at `p`, moves it to the front, and returns it.
+unsafe fn choose_pivot<T: Copy, F>(p: *mut T, len: usize, is_less: &mut F) -> T
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let (a, b, c) = (len / 4, len / 2, len / 4 * 3);
+    let pivot = if len >= NINTHER_THRESHOLD {
+        let a = median3(p, a - 1, a, a + 1, is_less);
+        let b = median3(p, b - 1, b, b + 1, is_less);
+        let c = median3(p, c - 1, c, c + 1, is_less);
+        median3(p, a, b, c, is_less)
+    } else {
+        median3(p, a, b, c, is_less)
+    };
+    ptr::swap(p, p.add(pivot));
+    p.read()
+}
+
+/// Moves the elements at `p` for which `goes_left` returns `true` before all others,
+/// returning how many there are.
+///
+/// This is a branchless Lomuto partition: every element is unconditionally swapped into
+/// place, and only the boundary moves depending on the comparison.
+unsafe fn partition<T: Copy, F>(p: *mut T, len: usize, mut goes_left: F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut left = 0;
+    for i in 0..len {
+        let x = p.add(i).read();
+        let is_left = goes_left(&x);
+        p.add(i).write(p.add(left).read());
+        p.add(left).write(x);
+        left += is_left as usize;
+    }
+    left
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use crate::SliceExists;
+    use alloc::vec::Vec;
+
+    /// A small deterministic xorshift generator.
+    fn random(seed: &mut u64) -> u64 {
+        *seed ^= *seed << 13;
+        *seed ^= *seed >> 7;
+        *seed ^= *seed << 17;
+        *seed
+    }
+
+    fn inputs() -> Vec<Vec<u32>> {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        let mut out = Vec::new();
+        for len in [0, 1, 2, 3, 19, 20, 21, 49, 50, 100, 1000, 5000] {
+            out.push((0..len).map(|_| random(&mut seed) as u32).collect());
+            out.push((0..len).map(|_| random(&mut seed) as u32 % 4).collect());
+            out.push((0..len).collect());
+            out.push((0..len).rev().collect());
+            out.push((0..len).map(|i| i % 7).collect());
+        }
+        out
+    }
+
+    #[test]
+    fn sort_unstable_matches_core() {
+        for mut v in inputs() {
+            let mut expected = v.clone();
+            expected.sort_unstable();
+            let s = SliceExists::from_mut(&mut v);
+            s.sort_unstable();
+            assert!(s.is_sorted());
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn sort_unstable_by_key() {
+        for mut v in inputs() {
+            let mut expected = v.clone();
+            expected.sort_unstable_by_key(|x| core::cmp::Reverse(*x));
+            SliceExists::from_mut(&mut v).sort_unstable_by_key(|x| core::cmp::Reverse(*x));
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn select_nth_unstable() {
+        for v in inputs() {
+            let mut sorted = v.clone();
+            sorted.sort_unstable();
+            for index in [0, v.len() / 3, v.len() / 2, v.len().saturating_sub(1)] {
+                if index >= v.len() {
+                    continue;
+                }
+                let mut v = v.clone();
+                let s = SliceExists::from_mut(&mut v);
+                let (before, nth, after) = s.select_nth_unstable(index);
+                assert_eq!(nth.get(), sorted[index]);
+                assert!(before.values().all(|x| x <= sorted[index]));
+                assert!(after.values().all(|x| x >= sorted[index]));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than length of slice")]
+    fn select_nth_unstable_out_of_bounds() {
+        let mut v = [1, 2, 3];
+        SliceExists::from_mut(&mut v).select_nth_unstable(3);
+    }
+
+    #[test]
+    fn is_sorted() {
+        assert!(SliceExists::<u8>::from_ref(&[]).is_sorted());
+        assert!(SliceExists::from_ref(&[1, 1, 2]).is_sorted());
+        assert!(!SliceExists::from_ref(&[2, 1]).is_sorted());
+        assert!(!SliceExists::from_ref(&[1.0, f64::NAN]).is_sorted());
+    }
+
+    #[test]
+    fn sort_stable_in_place() {
+        let mut seed = 7;
+        for v in inputs() {
+            let mut v: Vec<(u32, usize)> = v
+                .into_iter()
+                .map(|x| (x % 16, random(&mut seed) as usize))
+                .collect();
+            let mut expected = v.clone();
+            expected.sort_by_key(|&(k, _)| k);
+            super::sort_stable(SliceExists::from_mut(&mut v), |a, b| a.0 < b.0);
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut seed = 1;
+        let mut v: Vec<(u8, u32)> = (0..1000)
+            .map(|i| (random(&mut seed) as u8 % 8, i))
+            .collect();
+        let mut expected = v.clone();
+        expected.sort_by_key(|&(k, _)| k);
+        SliceExists::from_mut(&mut v).sort_by_key(|&(k, _)| k);
+        assert_eq!(v, expected);
+    }
+}
@@ -0,0 +1,752 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strided views over existential slices.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Index, IndexMut, RangeBounds};
+
+use super::index::{self, IndexError, IndexErrorKind};
+use super::{len_mismatch_fail, Copied, SliceExists};
+use crate::Exists;
+
+/// A view of every `n`th element of an existential slice, or of one field
+/// across a slice of structs.
+///
+/// Element `i` is located `i * stride()` bytes from element 0. The stride may be
+/// negative, in which case the view walks the underlying buffer backwards.
+///
+/// Created by [`SliceExists::step_by`] and [`SliceExists::project`].
+pub struct StridedSliceExists<'a, T> {
+    ptr: *const T,
+    len: usize,
+    stride: isize,
+    _phantom: PhantomData<&'a SliceExists<T>>,
+}
+
+/// A mutable view of every `n`th element of an existential slice, or of one field
+/// across a slice of structs.
+///
+/// Like `&mut SliceExists<T>`, several mutable strided views may overlap.
+///
+/// Created by [`SliceExists::step_by_mut`] and [`SliceExists::project_mut`].
+pub struct StridedSliceExistsMut<'a, T> {
+    ptr: *mut T,
+    len: usize,
+    stride: isize,
+    _phantom: PhantomData<&'a mut SliceExists<T>>,
+}
+
+/// Returns the number of elements in a view of `len` elements stepped by `step`.
+#[inline]
+#[track_caller]
+fn stepped_len(len: usize, step: usize) -> usize {
+    assert!(step != 0, "step must be non-zero");
+    len.div_ceil(step)
+}
+
+/// Returns the stride of a view stepped by `step` whose new length is `new_len`.
+///
+/// The stride of a view with at most one element is never used, so it is left
+/// unchanged rather than risking an overflow.
+#[inline]
+fn stepped_stride(stride: isize, step: usize, new_len: usize) -> isize {
+    if new_len > 1 {
+        stride * step as isize
+    } else {
+        stride
+    }
+}
+
+/// Returns the offset of the last element of a view, in bytes.
+#[inline]
+fn last_offset(len: usize, stride: isize) -> isize {
+    len.saturating_sub(1) as isize * stride
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn out_of_bounds_fail(index: usize, len: usize) -> ! {
+    panic!(
+        "{}",
+        IndexError::new(IndexErrorKind::OutOfBounds, index, len)
+    )
+}
+
+impl<'a, T> StridedSliceExists<'a, T> {
+    /// Constructs a strided view from its raw parts.
+    ///
+    /// # Safety
+    /// For every `i < len`, `data.byte_offset(i * stride)` must meet the requirements of
+    /// [`Exists::from_ptr`] for lifetime `'a`, and all of these elements must be
+    /// within a single allocated object.
+    #[inline]
+    pub unsafe fn from_raw_parts(data: *const T, len: usize, stride: isize) -> Self {
+        Self {
+            ptr: data,
+            len,
+            stride,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the distance between consecutive elements, in bytes.
+    #[inline]
+    pub fn stride(&self) -> isize {
+        self.stride
+    }
+
+    /// Returns a raw pointer to the first element of the view.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns an existential reference to the element at `index`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&'a Exists<T>> {
+        // Safety: `index` was just checked.
+        (index < self.len).then(|| unsafe { self.get_unchecked(index) })
+    }
+
+    /// Returns an existential reference to the element at `index`, without doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the result is unused.
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &'a Exists<T> {
+        Exists::from_ptr(self.ptr.byte_offset(index as isize * self.stride))
+    }
+
+    /// Returns an iterator over the elements of the view.
+    #[inline]
+    pub fn iter(&self) -> StridedIter<'a, T> {
+        StridedIter { v: *self }
+    }
+
+    /// Returns the subview covering `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn slice<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let range = index::range(range, self.len);
+        // Safety: the new elements are a subset of the old ones. An empty subview may
+        // start past the end of the buffer, so its address is computed with wrapping.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr
+                    .wrapping_byte_offset(range.start as isize * self.stride),
+                range.len(),
+                self.stride,
+            )
+        }
+    }
+
+    /// Returns a view of every `step`th element of this view, starting at the first.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn step_by(self, step: usize) -> Self {
+        let len = stepped_len(self.len, step);
+        // Safety: the new elements are a subset of the old ones.
+        unsafe { Self::from_raw_parts(self.ptr, len, stepped_stride(self.stride, step, len)) }
+    }
+
+    /// Returns the same elements in reverse order.
+    #[inline]
+    pub fn rev(self) -> Self {
+        // Safety: the elements are the same as the old ones.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr.byte_offset(last_offset(self.len, self.stride)),
+                self.len,
+                -self.stride,
+            )
+        }
+    }
+}
+
+impl<'a, T: Copy> StridedSliceExists<'a, T> {
+    /// Returns an iterator that reads each element of the view by value.
+    #[inline]
+    pub fn values(&self) -> Copied<StridedIter<'a, T>> {
+        Copied::new(self.iter())
+    }
+
+    /// Copies the elements of the view into `dst`, in order.
+    ///
+    /// The elements are copied one at a time, so `dst` may overlap the view.
+    ///
+    /// # Panics
+    /// Panics if the two have different lengths.
+    #[track_caller]
+    pub fn copy_to(&self, dst: &mut SliceExists<T>) {
+        if self.len != dst.len() {
+            len_mismatch_fail(dst.len(), self.len);
+        }
+        for (d, s) in dst.iter_mut().zip(self.iter()) {
+            d.set(s.get());
+        }
+    }
+}
+
+impl<T> Clone for StridedSliceExists<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StridedSliceExists<'_, T> {}
+
+impl<T> Index<usize> for StridedSliceExists<'_, T> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: usize) -> &Exists<T> {
+        match self.get(index) {
+            Some(e) => e,
+            None => out_of_bounds_fail(index, self.len),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for StridedSliceExists<'a, T> {
+    type Item = &'a Exists<T>;
+    type IntoIter = StridedIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> StridedIter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> StridedSliceExistsMut<'a, T> {
+    /// Constructs a mutable strided view from its raw parts.
+    ///
+    /// # Safety
+    /// For every `i < len`, `data.byte_offset(i * stride)` must meet the requirements of
+    /// [`Exists::from_mut_ptr`] for lifetime `'a`, and all of these elements must be
+    /// within a single allocated object.
+    #[inline]
+    pub unsafe fn from_raw_parts(data: *mut T, len: usize, stride: isize) -> Self {
+        Self {
+            ptr: data,
+            len,
+            stride,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the distance between consecutive elements, in bytes.
+    #[inline]
+    pub fn stride(&self) -> isize {
+        self.stride
+    }
+
+    /// Returns a raw mutable pointer to the first element of the view.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns a shared view of the same elements.
+    #[inline]
+    pub fn as_strided(&self) -> StridedSliceExists<'_, T> {
+        // Safety: the elements are valid for writes, and so also for reads.
+        unsafe { StridedSliceExists::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// Reborrows the view, so that it can be passed on without being consumed.
+    #[inline]
+    pub fn reborrow(&mut self) -> StridedSliceExistsMut<'_, T> {
+        // Safety: the elements are the same as the old ones, for a shorter lifetime.
+        unsafe { StridedSliceExistsMut::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// Converts the view into a shared view of the same elements.
+    #[inline]
+    pub fn into_strided(self) -> StridedSliceExists<'a, T> {
+        // Safety: the elements are valid for writes, and so also for reads.
+        unsafe { StridedSliceExists::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// Returns an existential reference to the element at `index`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Exists<T>> {
+        self.as_strided().get(index)
+    }
+
+    /// Returns a mutable existential reference to the element at `index`,
+    /// or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Exists<T>> {
+        // Safety: `index` was just checked.
+        (index < self.len).then(|| unsafe { self.get_unchecked_mut(index) })
+    }
+
+    /// Returns a mutable existential reference to the element at `index`,
+    /// without doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the result is unused.
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut Exists<T> {
+        Exists::from_mut_ptr(self.ptr.byte_offset(index as isize * self.stride))
+    }
+
+    /// Returns an iterator over the elements of the view.
+    #[inline]
+    pub fn iter(&self) -> StridedIter<'_, T> {
+        self.as_strided().iter()
+    }
+
+    /// Returns an iterator over mutable existential references to the elements of the view.
+    #[inline]
+    pub fn iter_mut(&mut self) -> StridedIterMut<'_, T> {
+        StridedIterMut { v: self.reborrow() }
+    }
+
+    /// Returns the subview covering `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds, with the same messages as slice indexing.
+    #[inline]
+    #[track_caller]
+    pub fn slice<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let range = index::range(range, self.len);
+        // Safety: the new elements are a subset of the old ones. An empty subview may
+        // start past the end of the buffer, so its address is computed with wrapping.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr
+                    .wrapping_byte_offset(range.start as isize * self.stride),
+                range.len(),
+                self.stride,
+            )
+        }
+    }
+
+    /// Returns a view of every `step`th element of this view, starting at the first.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn step_by(self, step: usize) -> Self {
+        let len = stepped_len(self.len, step);
+        // Safety: the new elements are a subset of the old ones.
+        unsafe { Self::from_raw_parts(self.ptr, len, stepped_stride(self.stride, step, len)) }
+    }
+
+    /// Returns the same elements in reverse order.
+    #[inline]
+    pub fn rev(self) -> Self {
+        // Safety: the elements are the same as the old ones.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr.byte_offset(last_offset(self.len, self.stride)),
+                self.len,
+                -self.stride,
+            )
+        }
+    }
+}
+
+impl<T: Copy> StridedSliceExistsMut<'_, T> {
+    /// Returns an iterator that reads each element of the view by value.
+    #[inline]
+    pub fn values(&self) -> Copied<StridedIter<'_, T>> {
+        Copied::new(self.iter())
+    }
+
+    /// Copies the elements of the view into `dst`, in order.
+    ///
+    /// See [`StridedSliceExists::copy_to`].
+    #[inline]
+    #[track_caller]
+    pub fn copy_to(&self, dst: &mut SliceExists<T>) {
+        self.as_strided().copy_to(dst)
+    }
+
+    /// Copies all elements from `src` into the view, in order.
+    ///
+    /// The elements are copied one at a time, so `src` may overlap the view.
+    ///
+    /// # Panics
+    /// Panics if the two have different lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// let mut x = [0u8; 6];
+    /// let s = SliceExists::from_mut(&mut x);
+    /// s.step_by_mut(2).copy_from(SliceExists::from_ref(&[1, 2, 3]));
+    /// assert_eq!(x, [1, 0, 2, 0, 3, 0]);
+    /// ```
+    #[track_caller]
+    pub fn copy_from(&mut self, src: &SliceExists<T>) {
+        if self.len != src.len() {
+            len_mismatch_fail(self.len, src.len());
+        }
+        for (d, s) in self.iter_mut().zip(src.iter()) {
+            d.set(s.get());
+        }
+    }
+
+    /// Fills the view with `value`.
+    #[inline]
+    pub fn fill(&mut self, value: T) {
+        for e in self.iter_mut() {
+            e.set(value);
+        }
+    }
+}
+
+impl<T> Index<usize> for StridedSliceExistsMut<'_, T> {
+    type Output = Exists<T>;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: usize) -> &Exists<T> {
+        match self.get(index) {
+            Some(e) => e,
+            None => out_of_bounds_fail(index, self.len),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for StridedSliceExistsMut<'_, T> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut Exists<T> {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(e) => e,
+            None => out_of_bounds_fail(index, len),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for StridedSliceExistsMut<'a, T> {
+    type Item = &'a mut Exists<T>;
+    type IntoIter = StridedIterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> StridedIterMut<'a, T> {
+        StridedIterMut { v: self }
+    }
+}
+
+/// An iterator over the elements of a strided view.
+///
+/// Created by [`StridedSliceExists::iter`].
+pub struct StridedIter<'a, T> {
+    v: StridedSliceExists<'a, T>,
+}
+
+impl<'a, T> Iterator for StridedIter<'a, T> {
+    type Item = &'a Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a Exists<T>> {
+        let first = self.v.get(0)?;
+        // The pointer may step past the end of the buffer here, but is never used again
+        // if so, since the view is then empty.
+        self.v.ptr = self.v.ptr.wrapping_byte_offset(self.v.stride);
+        self.v.len -= 1;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.v.len, Some(self.v.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for StridedIter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a Exists<T>> {
+        self.v.len = self.v.len.checked_sub(1)?;
+        // Safety: the old length was in bounds.
+        Some(unsafe { self.v.get_unchecked(self.v.len) })
+    }
+}
+
+impl<T> ExactSizeIterator for StridedIter<'_, T> {}
+impl<T> FusedIterator for StridedIter<'_, T> {}
+
+/// An iterator over mutable existential references to the elements of a strided view.
+///
+/// Created by [`StridedSliceExistsMut::iter_mut`].
+pub struct StridedIterMut<'a, T> {
+    v: StridedSliceExistsMut<'a, T>,
+}
+
+impl<'a, T> Iterator for StridedIterMut<'a, T> {
+    type Item = &'a mut Exists<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut Exists<T>> {
+        if self.v.len == 0 {
+            return None;
+        }
+        // Safety: the view is non-empty, and its elements are valid for `'a`.
+        let first = unsafe { Exists::from_mut_ptr(self.v.ptr) };
+        // The pointer may step past the end of the buffer here, but is never used again
+        // if so, since the view is then empty.
+        self.v.ptr = self.v.ptr.wrapping_byte_offset(self.v.stride);
+        self.v.len -= 1;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.v.len, Some(self.v.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for StridedIterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut Exists<T>> {
+        self.v.len = self.v.len.checked_sub(1)?;
+        let offset = self.v.len as isize * self.v.stride;
+        // Safety: the old length was in bounds, and its elements are valid for `'a`.
+        Some(unsafe { Exists::from_mut_ptr(self.v.ptr.byte_offset(offset)) })
+    }
+}
+
+impl<T> ExactSizeIterator for StridedIterMut<'_, T> {}
+impl<T> FusedIterator for StridedIterMut<'_, T> {}
+
+impl<T> SliceExists<T> {
+    /// Returns a view of every `step`th element of the slice, starting at the first.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// // The second column of a 3x3 row-major matrix.
+    /// let m = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    /// let col = SliceExists::from_ref(&m[1..]).step_by(3);
+    /// assert_eq!(col.values().collect::<Vec<_>>(), [2, 5, 8]);
+    /// assert_eq!(col.rev()[0].get(), 8);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn step_by(&self, step: usize) -> StridedSliceExists<'_, T> {
+        let len = stepped_len(self.len(), step);
+        let stride = stepped_stride(mem::size_of::<T>() as isize, step, len);
+        // Safety: every element of the view is an element of `self`.
+        unsafe { StridedSliceExists::from_raw_parts(self.as_ptr(), len, stride) }
+    }
+
+    /// Returns a mutable view of every `step`th element of the slice, starting at the first.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0.
+    #[inline]
+    #[track_caller]
+    pub fn step_by_mut(&mut self, step: usize) -> StridedSliceExistsMut<'_, T> {
+        let len = stepped_len(self.len(), step);
+        let stride = stepped_stride(mem::size_of::<T>() as isize, step, len);
+        // Safety: every element of the view is an element of `self`.
+        unsafe { StridedSliceExistsMut::from_raw_parts(self.as_mut_ptr(), len, stride) }
+    }
+
+    /// Returns a view of the field of type `F` at byte `offset` in each element of the slice.
+    ///
+    /// # Safety
+    /// `offset` must be the offset of a field of type `F` within `T`, such as one
+    /// returned by [`core::mem::offset_of!`]. This holds even if the slice is empty,
+    /// in which case the view is empty and its data pointer is never dereferenced.
+    ///
+    /// # Examples
+    /// ```
+    /// # use exists_ref::SliceExists;
+    /// # use core::mem::offset_of;
+    /// struct Point { x: f32, y: f32 }
+    /// let pts = [Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }];
+    /// let ys = unsafe { SliceExists::from_ref(&pts).project::<f32>(offset_of!(Point, y)) };
+    /// assert_eq!(ys[1].get(), 4.0);
+    /// ```
+    #[inline]
+    pub unsafe fn project<F>(&self, offset: usize) -> StridedSliceExists<'_, F> {
+        // An empty slice's pointer may be dangling or one past the end, so the offset
+        // must not assume it is in bounds.
+        let data = self.as_ptr().wrapping_byte_add(offset) as *const F;
+        StridedSliceExists::from_raw_parts(data, self.len(), mem::size_of::<T>() as isize)
+    }
+
+    /// Returns a mutable view of the field of type `F` at byte `offset` in each
+    /// element of the slice.
+    ///
+    /// # Safety
+    /// `offset` must be the offset of a field of type `F` within `T`, such as one
+    /// returned by [`core::mem::offset_of!`]. This holds even if the slice is empty,
+    /// in which case the view is empty and its data pointer is never dereferenced.
+    #[inline]
+    pub unsafe fn project_mut<F>(&mut self, offset: usize) -> StridedSliceExistsMut<'_, F> {
+        // See `project`.
+        let data = self.as_mut_ptr().wrapping_byte_add(offset) as *mut F;
+        StridedSliceExistsMut::from_raw_parts(data, self.len(), mem::size_of::<T>() as isize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn step_by() {
+        let x = [0, 1, 2, 3, 4, 5, 6];
+        let s = SliceExists::from_ref(&x);
+        assert!(s.step_by(1).values().eq(x));
+        assert!(s.step_by(3).values().eq([0, 3, 6]));
+        assert!(s.step_by(4).values().eq([0, 4]));
+        assert!(s.step_by(7).values().eq([0]));
+        assert!(s.step_by(usize::MAX).values().eq([0]));
+        assert!(s.step_by(2).step_by(2).values().eq([0, 4]));
+        assert!(s[..0].step_by(2).is_empty());
+        assert_eq!(
+            s.step_by(2)
+                .iter()
+                .rev()
+                .map(|e| e.get())
+                .collect::<Vec<_>>(),
+            [6, 4, 2, 0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    fn step_by_zero() {
+        SliceExists::from_ref(&[1]).step_by(0);
+    }
+
+    #[test]
+    fn rev_and_slice() {
+        let x = [0, 1, 2, 3, 4, 5, 6];
+        let s = SliceExists::from_ref(&x);
+        let v = s.step_by(2).rev();
+        assert_eq!(v.stride(), -8);
+        assert!(v.values().eq([6, 4, 2, 0]));
+        assert!(v.slice(1..3).values().eq([4, 2]));
+        assert!(v.slice(1..).rev().values().eq([0, 2, 4]));
+        assert!(s[..0].step_by(1).rev().is_empty());
+        assert!(v.get(4).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn index_out_of_bounds() {
+        let x = [0, 1, 2];
+        let _ = &SliceExists::from_ref(&x).step_by(2)[2];
+    }
+
+    #[test]
+    fn mutate_overlapping() {
+        let mut x = [1, 2, 3, 4, 5, 6];
+        let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+        let mut evens = a.step_by_mut(2);
+        let mut odds = b[1..].step_by_mut(2);
+        for (e, o) in evens.iter_mut().zip(odds.iter_mut()) {
+            e.swap(o);
+        }
+        odds.reborrow().rev().fill(0);
+        evens[2].set(50);
+        assert_eq!(x, [2, 0, 4, 0, 50, 0]);
+    }
+
+    #[test]
+    fn copy_overlapping() {
+        let mut x = [1, 2, 3, 4, 5, 6];
+        let [a, b] = SliceExists::from_mut(&mut x).copy_mut();
+        // Reads and writes alternate, so the result reflects earlier writes.
+        a.step_by(2).copy_to(&mut b[2..5]);
+        assert_eq!(x, [1, 2, 1, 1, 5, 6]);
+
+        let mut y = [1, 2, 3, 4];
+        let [a, b] = SliceExists::from_mut(&mut y).copy_mut();
+        a.step_by_mut(1).rev().copy_from(b);
+        assert_eq!(y, [1, 2, 2, 1]);
+    }
+
+    #[test]
+    fn project() {
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        struct Particle {
+            pos: [f32; 2],
+            mass: u8,
+        }
+        let mut ps = [Particle {
+            pos: [0.0; 2],
+            mass: 1,
+        }; 3];
+        let s = SliceExists::from_mut(&mut ps);
+        let mut masses = unsafe { s.project_mut::<u8>(mem::offset_of!(Particle, mass)) };
+        assert_eq!(masses.stride(), mem::size_of::<Particle>() as isize);
+        masses.copy_from(SliceExists::from_ref(&[4, 5, 6]));
+        let pos = unsafe { s.project::<[f32; 2]>(mem::offset_of!(Particle, pos)) };
+        assert_eq!(pos[2].get(), [0.0; 2]);
+        assert_eq!(ps.map(|p| p.mass), [4, 5, 6]);
+
+        let empty = <&mut SliceExists<Particle>>::default();
+        let masses = unsafe { empty.project_mut::<u8>(mem::offset_of!(Particle, mass)) };
+        assert!(masses.is_empty());
+    }
+
+    #[test]
+    fn zero_sized() {
+        let x = [(); 5];
+        let v = SliceExists::from_ref(&x).step_by(2);
+        assert_eq!((v.len(), v.stride()), (3, 0));
+        assert_eq!(v.iter().count(), 3);
+    }
+}
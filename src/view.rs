@@ -0,0 +1,461 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed views of existential byte slices, without copying.
+//!
+//! A type implementing [`FromBytes`] can be read from any bytes, so `SliceExists<u8>`
+//! methods like [`view_prefix`](SliceExists::view_prefix) can return an existential
+//! reference to one in place, after checking that the bytes are long enough and
+//! aligned for it. The [`from_bytes!`](crate::from_bytes!) macro implements
+//! `FromBytes` for `repr(C)` structs whose fields all implement it.
+//!
+//! Types implementing [`Unaligned`] have an alignment of 1, and the `_unaligned`
+//! methods like [`view_at_unaligned`](SliceExists::view_at_unaligned) accept only
+//! those, so they can fail only on length. [`Unalign<T>`] wraps any `FromBytes` type
+//! to make it `Unaligned`, for bytes at arbitrary offsets.
+//!
+//! These views are shared only: writing a typed value could write padding bytes,
+//! which would leave the byte slice uninitialized.
+//!
+//! # Examples
+//! ```
+//! use exists_ref::{field, from_bytes, view::Unalign, SliceExists};
+//!
+//! from_bytes! {
+//!     #[derive(Clone, Copy)]
+//!     pub struct UdpHeader {
+//!         pub src_port: [u8; 2],
+//!         pub dst_port: [u8; 2],
+//!         pub len: [u8; 2],
+//!         pub checksum: [u8; 2],
+//!     }
+//! }
+//!
+//! let packet = [0x00, 0x35, 0x04, 0xd2, 0x00, 0x0c, 0x00, 0x00, b'p', b'i', b'n', b'g'];
+//! let bytes = SliceExists::from_ref(&packet);
+//! let hdr = bytes.view_prefix::<UdpHeader>()?;
+//! assert_eq!(u16::from_be_bytes(hdr.field(field!(UdpHeader, dst_port)).get()), 1234);
+//! assert_eq!(bytes.view_suffix::<[u8; 4]>()?.get(), *b"ping");
+//!
+//! // `u32` may not be aligned at offset 1, but `Unalign<u32>` always is.
+//! let x = bytes.view_at_unaligned::<Unalign<u32>>(1)?.get().into_inner();
+//! assert_eq!(x, u32::from_ne_bytes([0x35, 0x04, 0xd2, 0x00]));
+//! # Ok::<(), exists_ref::view::ViewError>(())
+//! ```
+
+use core::{fmt, mem};
+
+use crate::{Exists, SliceExists};
+
+/// A type for which any initialized bytes of the right length are a valid value.
+///
+/// # Safety
+/// Every sequence of `size_of::<Self>()` initialized bytes must be a valid `Self`.
+/// This rules out types with invalid bit patterns, such as `bool`, `char`, enums,
+/// references and `NonZero` integers. Padding bytes are allowed.
+pub unsafe trait FromBytes {}
+
+/// A type with an alignment of 1, which a byte slice is always aligned for.
+///
+/// # Safety
+/// `align_of::<Self>()` must be 1.
+pub unsafe trait Unaligned {}
+
+macro_rules! impl_from_bytes {
+    ($($t:ty)*) => {$(
+        // Safety: every bit pattern is a valid integer or float.
+        unsafe impl FromBytes for $t {}
+    )*};
+}
+
+impl_from_bytes!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+// Safety: `()` has no bytes.
+unsafe impl FromBytes for () {}
+
+// Safety: an array has no padding, and every element is valid for any bytes.
+unsafe impl<T: FromBytes, const N: usize> FromBytes for [T; N] {}
+
+// Safety: these have an alignment of 1.
+unsafe impl Unaligned for u8 {}
+unsafe impl Unaligned for i8 {}
+unsafe impl Unaligned for () {}
+unsafe impl<T: Unaligned, const N: usize> Unaligned for [T; N] {}
+
+/// A `T` stored without any alignment requirement.
+///
+/// A field cannot be borrowed from a packed struct, so this is read by value with
+/// [`into_inner`](Unalign::into_inner), or with [`Exists::get`] when `T: Copy`.
+#[repr(C, packed)]
+pub struct Unalign<T>(T);
+
+impl<T> Unalign<T> {
+    /// Wraps `value`.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Copy> Clone for Unalign<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy> Copy for Unalign<T> {}
+
+// Safety: `Unalign<T>` has the same bytes as `T`.
+unsafe impl<T: FromBytes> FromBytes for Unalign<T> {}
+
+// Safety: `Unalign<T>` is `repr(packed)`.
+unsafe impl<T> Unaligned for Unalign<T> {}
+
+/// Implements [`FromBytes`] for struct definitions whose fields all implement it.
+///
+/// Each struct is given `#[repr(C)]`, and fails to compile if a field's type does not
+/// implement `FromBytes`. Other attributes, including `#[repr(packed)]`, are kept.
+/// Generic structs are not supported.
+///
+/// # Examples
+/// ```
+/// use exists_ref::{from_bytes, view::FromBytes};
+///
+/// from_bytes! {
+///     /// A point on the plane.
+///     pub struct Point {
+///         pub x: f32,
+///         pub y: f32,
+///     }
+///
+///     struct Polygon {
+///         corners: [Point; 3],
+///         color: u32,
+///     }
+/// }
+///
+/// fn assert_from_bytes<T: FromBytes>() {}
+/// assert_from_bytes::<Polygon>();
+/// ```
+///
+/// ```compile_fail
+/// exists_ref::from_bytes! {
+///     struct Flag {
+///         set: bool,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! from_bytes {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    )*) => {$(
+        $(#[$meta])*
+        #[repr(C)]
+        $vis struct $name {
+            $($(#[$field_meta])* $field_vis $field: $ty,)*
+        }
+
+        // Safety: `$name` is `repr(C)`, so its bytes are those of its fields and
+        // padding, and every field implements `FromBytes`.
+        unsafe impl $crate::view::FromBytes for $name {}
+
+        const _: () = {
+            fn assert_from_bytes<T: $crate::view::FromBytes>() {}
+            #[allow(dead_code)]
+            fn assert_fields() {
+                $(assert_from_bytes::<$ty>();)*
+            }
+        };
+    )*};
+}
+
+/// The error returned when a typed view of a byte slice cannot be created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ViewError {
+    /// The byte slice is shorter than the bytes needed.
+    Length {
+        /// The number of bytes needed, which saturates at `usize::MAX`.
+        needed: usize,
+        /// The length of the byte slice.
+        len: usize,
+    },
+    /// The bytes are not aligned for the type.
+    Alignment {
+        /// The alignment of the type.
+        align: usize,
+    },
+}
+
+impl fmt::Display for ViewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ViewError::Length { needed, len } => write!(
+                f,
+                "{} bytes needed but the slice has length {}",
+                needed, len
+            ),
+            ViewError::Alignment { align } => {
+                write!(f, "bytes are not aligned to {} bytes", align)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ViewError {}
+
+impl SliceExists<u8> {
+    /// Returns the `len` bytes at `offset`, checking that they are aligned for `T`.
+    #[inline]
+    fn view_bytes<T>(&self, offset: usize, len: usize) -> Result<*const T, ViewError> {
+        let needed = offset.saturating_add(len);
+        if needed > self.len() {
+            return Err(ViewError::Length {
+                needed,
+                len: self.len(),
+            });
+        }
+        // Safety: `offset + len` is in bounds.
+        let p = unsafe { self.as_ptr().add(offset) }.cast::<T>();
+        if !p.is_aligned() {
+            return Err(ViewError::Alignment {
+                align: mem::align_of::<T>(),
+            });
+        }
+        Ok(p)
+    }
+
+    /// Returns an existential reference to a `T` in the first bytes of the slice.
+    ///
+    /// # Errors
+    /// Returns an error if the slice is too short or not aligned for `T`.
+    #[inline]
+    pub fn view_prefix<T: FromBytes>(&self) -> Result<&Exists<T>, ViewError> {
+        self.view_at(0)
+    }
+
+    /// Returns an existential reference to a `T` in the last bytes of the slice.
+    ///
+    /// # Errors
+    /// Returns an error if the slice is too short, or the last bytes are not aligned
+    /// for `T`.
+    #[inline]
+    pub fn view_suffix<T: FromBytes>(&self) -> Result<&Exists<T>, ViewError> {
+        let size = mem::size_of::<T>();
+        match self.len().checked_sub(size) {
+            Some(offset) => self.view_at(offset),
+            None => Err(ViewError::Length {
+                needed: size,
+                len: self.len(),
+            }),
+        }
+    }
+
+    /// Returns an existential reference to a `T` in the bytes at `offset`.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are out of bounds or not aligned for `T`.
+    #[inline]
+    pub fn view_at<T: FromBytes>(&self, offset: usize) -> Result<&Exists<T>, ViewError> {
+        let p = self.view_bytes::<T>(offset, mem::size_of::<T>())?;
+        // Safety: `p` is in bounds and aligned, and any bytes are a valid `T`.
+        Ok(unsafe { Exists::from_ptr(p) })
+    }
+
+    /// Returns an existential slice of `count` values of `T` in the first bytes of
+    /// the slice.
+    ///
+    /// # Errors
+    /// Returns an error if the slice is too short or not aligned for `T`. The bytes
+    /// needed saturate at `usize::MAX` if `count * size_of::<T>()` overflows.
+    #[inline]
+    pub fn view_slice<T: FromBytes>(&self, count: usize) -> Result<&SliceExists<T>, ViewError> {
+        let len = count.saturating_mul(mem::size_of::<T>());
+        let p = self.view_bytes::<T>(0, len)?;
+        // Safety: `count` values of `T` are in bounds and aligned, and any bytes are
+        // valid values.
+        Ok(unsafe { SliceExists::from_ptr(core::ptr::slice_from_raw_parts(p, count)) })
+    }
+
+    /// Returns an existential reference to an unaligned `T` in the first bytes of the
+    /// slice.
+    ///
+    /// # Errors
+    /// Returns a [`ViewError::Length`] error if the slice is too short. Since `T` is
+    /// [`Unaligned`], this never fails the alignment check.
+    #[inline]
+    pub fn view_prefix_unaligned<T: FromBytes + Unaligned>(&self) -> Result<&Exists<T>, ViewError> {
+        self.view_prefix()
+    }
+
+    /// Returns an existential reference to an unaligned `T` in the last bytes of the
+    /// slice.
+    ///
+    /// # Errors
+    /// Returns a [`ViewError::Length`] error if the slice is too short. Since `T` is
+    /// [`Unaligned`], this never fails the alignment check.
+    #[inline]
+    pub fn view_suffix_unaligned<T: FromBytes + Unaligned>(&self) -> Result<&Exists<T>, ViewError> {
+        self.view_suffix()
+    }
+
+    /// Returns an existential reference to an unaligned `T` in the bytes at `offset`.
+    ///
+    /// # Errors
+    /// Returns a [`ViewError::Length`] error if the bytes are out of bounds. Since `T`
+    /// is [`Unaligned`], this never fails the alignment check.
+    #[inline]
+    pub fn view_at_unaligned<T: FromBytes + Unaligned>(
+        &self,
+        offset: usize,
+    ) -> Result<&Exists<T>, ViewError> {
+        self.view_at(offset)
+    }
+
+    /// Returns an existential slice of `count` unaligned values of `T` in the first
+    /// bytes of the slice.
+    ///
+    /// # Errors
+    /// Returns a [`ViewError::Length`] error if the slice is too short. Since `T` is
+    /// [`Unaligned`], this never fails the alignment check.
+    #[inline]
+    pub fn view_slice_unaligned<T: FromBytes + Unaligned>(
+        &self,
+        count: usize,
+    ) -> Result<&SliceExists<T>, ViewError> {
+        self.view_slice(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::from_bytes! {
+        #[derive(Clone, Copy)]
+        struct Header {
+            kind: u16,
+            flags: u8,
+            len: u32,
+        }
+
+        #[derive(Clone, Copy)]
+        #[repr(packed)]
+        struct Packed {
+            a: u8,
+            b: u32,
+        }
+    }
+
+    #[repr(C, align(8))]
+    struct Aligned<const N: usize>([u8; N]);
+
+    #[test]
+    fn views() {
+        let mut buf = Aligned([0u8; 24]);
+        buf.0[..2].copy_from_slice(&7u16.to_ne_bytes());
+        buf.0[4..8].copy_from_slice(&99u32.to_ne_bytes());
+        buf.0[20..].copy_from_slice(&5u32.to_ne_bytes());
+        let s = SliceExists::from_ref(&buf.0);
+        let h = s.view_prefix::<Header>().unwrap().get();
+        assert_eq!((h.kind, h.flags, h.len), (7, 0, 99));
+        assert_eq!(s.view_suffix::<u32>().unwrap().get(), 5);
+        assert_eq!(s.view_at::<u32>(4).unwrap().get(), 99);
+        let words = s.view_slice::<u32>(6).unwrap();
+        assert_eq!((words.len(), words[1].get(), words[5].get()), (6, 99, 5));
+        assert!(s.view_slice::<u32>(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let buf = Aligned([0u8; 12]);
+        let s = SliceExists::from_ref(&buf.0);
+        assert_eq!(
+            s.view_at::<u32>(2).err().unwrap(),
+            ViewError::Alignment { align: 4 }
+        );
+        assert_eq!(
+            s.view_at::<u32>(10).err().unwrap(),
+            ViewError::Length {
+                needed: 14,
+                len: 12
+            }
+        );
+        assert_eq!(
+            s[..7].view_suffix::<Header>().err().unwrap(),
+            ViewError::Length { needed: 8, len: 7 }
+        );
+        assert_eq!(
+            s.view_slice::<u16>(usize::MAX).err().unwrap(),
+            ViewError::Length {
+                needed: usize::MAX,
+                len: 12
+            }
+        );
+        assert_eq!(
+            s.view_at::<u8>(usize::MAX).err().unwrap(),
+            ViewError::Length {
+                needed: usize::MAX,
+                len: 12
+            }
+        );
+    }
+
+    #[test]
+    fn unaligned() {
+        let mut buf = Aligned([0u8; 9]);
+        buf.0[1..5].copy_from_slice(&0x0102_0304u32.to_ne_bytes());
+        buf.0[5] = 0xff;
+        let s = SliceExists::from_ref(&buf.0);
+        for offset in 0..5 {
+            assert!(s.view_at_unaligned::<Unalign<u32>>(offset).is_ok());
+            assert!(s.view_at_unaligned::<[u8; 3]>(offset).is_ok());
+        }
+        assert_eq!(
+            s.view_at_unaligned::<Unalign<u32>>(1)
+                .unwrap()
+                .get()
+                .into_inner(),
+            0x0102_0304
+        );
+        assert_eq!(
+            s.view_at_unaligned::<Unalign<u32>>(6).err().unwrap(),
+            ViewError::Length { needed: 10, len: 9 }
+        );
+        let words = s[1..].view_slice_unaligned::<Unalign<u16>>(4).unwrap();
+        assert_eq!(
+            words[0].get().into_inner(),
+            u16::from_ne_bytes([buf.0[1], buf.0[2]])
+        );
+        assert_eq!(
+            s[1..].view_prefix_unaligned::<[u8; 2]>().unwrap().get(),
+            [buf.0[1], buf.0[2]]
+        );
+        assert_eq!(s[..6].view_suffix_unaligned::<u8>().unwrap().get(), 0xff);
+        let p = s[4..].view_prefix::<Packed>().unwrap().get();
+        assert_eq!(({ p.a }, { p.b }), (buf.0[4], 0xff));
+        assert_eq!(mem::align_of::<Packed>(), 1);
+    }
+}